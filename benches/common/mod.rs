@@ -0,0 +1,14 @@
+//! Input data shared by the benchmarks.
+
+/// Return `len` bytes of pseudorandom data, the same for every run.
+pub fn data(len: usize) -> Vec<u8> {
+    let mut state = 0x5EED_BABB_1E00_0001_u64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
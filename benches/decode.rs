@@ -0,0 +1,38 @@
+//! Compare the table-driven decoder against the original implementation.
+//!
+//! Run with `cargo bench --bench decode`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+mod common;
+#[path = "../tests/reference/mod.rs"]
+mod reference;
+
+const SIZES: [(&str, usize); 4] = [
+    ("16 B", 16),
+    ("32 B", 32),
+    ("1 KiB", 1024),
+    ("1 MiB", 1024 * 1024),
+];
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for (name, len) in SIZES {
+        let encoded = boba::encode(common::data(len));
+        group.throughput(Throughput::Bytes(encoded.len() as u64));
+        group.bench_with_input(BenchmarkId::new("boba", name), &encoded, |b, encoded| {
+            b.iter(|| boba::decode(black_box(encoded)));
+        });
+        group.bench_with_input(
+            BenchmarkId::new("reference", name),
+            &encoded,
+            |b, encoded| b.iter(|| reference::decode(black_box(encoded.as_bytes()))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+//! Compare the table-driven encoder against the original implementation.
+//!
+//! Run with `cargo bench --bench encode`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+mod common;
+#[path = "../tests/reference/mod.rs"]
+mod reference;
+
+const SIZES: [(&str, usize); 4] = [
+    ("16 B", 16),
+    ("32 B", 32),
+    ("1 KiB", 1024),
+    ("1 MiB", 1024 * 1024),
+];
+
+fn bench_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    for (name, len) in SIZES {
+        let data = common::data(len);
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("boba", name), &data, |b, data| {
+            b.iter(|| boba::encode(black_box(data)));
+        });
+        group.bench_with_input(BenchmarkId::new("reference", name), &data, |b, data| {
+            b.iter(|| reference::encode(black_box(data)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_encode_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode_batch");
+    let ids = common::data(16 * 10_000);
+    group.throughput(Throughput::Elements(10_000));
+    group.bench_function("encode_batch 16 B ids", |b| {
+        b.iter(|| boba::encode_batch(black_box(&ids).chunks_exact(16)));
+    });
+    group.bench_function("encode 16 B ids", |b| {
+        b.iter(|| {
+            black_box(&ids)
+                .chunks_exact(16)
+                .map(boba::encode)
+                .collect::<Vec<_>>()
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_encode_batch);
+criterion_main!(benches);
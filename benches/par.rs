@@ -0,0 +1,48 @@
+//! Compare parallel and sequential encoding and decoding of large inputs.
+//!
+//! Run with `cargo bench --features rayon --bench par`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+mod common;
+
+const SIZES: [(&str, usize); 2] = [("1 MiB", 1024 * 1024), ("16 MiB", 16 * 1024 * 1024)];
+
+fn bench_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("par_encode");
+    for (name, len) in SIZES {
+        let data = common::data(len);
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("par_encode", name), &data, |b, data| {
+            b.iter(|| boba::par_encode(black_box(data)));
+        });
+        group.bench_with_input(BenchmarkId::new("encode", name), &data, |b, data| {
+            b.iter(|| boba::encode(black_box(data)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("par_decode");
+    for (name, len) in SIZES {
+        let encoded = boba::encode(common::data(len));
+        group.throughput(Throughput::Bytes(encoded.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("par_decode", name),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| boba::par_decode(black_box(encoded)));
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("decode", name), &encoded, |b, encoded| {
+            b.iter(|| boba::decode(black_box(encoded)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_decode);
+criterion_main!(benches);
@@ -0,0 +1,329 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::decode::is_alphabet;
+
+const SEPARATOR: char = '-';
+
+/// How the hidden words of an [`Abbreviation`] are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Elision<'a> {
+    /// Replace all hidden words with a single placeholder, e.g.
+    /// `xigak-nyryk-…-sonax`.
+    Ellipsis(&'a str),
+    /// Replace every letter of each hidden word with a mask character, e.g.
+    /// `xigak-*****-*****-bosek-sonax`.
+    Mask(char),
+}
+
+/// Render an abbreviated form of a Bubble Babble encoding that only shows its
+/// first and last words.
+///
+/// Abbreviations are useful for displaying fingerprints in dashboards and
+/// logs. Use [`boba::matches_abbreviated`](crate::matches_abbreviated) to
+/// check that an abbreviation is consistent with some data. Only
+/// abbreviations that show at least one leading and one trailing word can be
+/// checked.
+///
+/// If the encoding has no more words than the abbreviation would show, it is
+/// rendered in full.
+///
+/// # Examples
+///
+/// ```
+/// use boba::Abbreviation;
+///
+/// let abbrev = Abbreviation::new(2, 1);
+/// assert_eq!(abbrev.encode("Pineapple"), "xigak-nyryk-…-sonax");
+///
+/// let abbrev = Abbreviation::new(2, 1).with_ellipsis("...");
+/// assert_eq!(abbrev.encode("Pineapple"), "xigak-nyryk-...-sonax");
+///
+/// let abbrev = Abbreviation::new(1, 2).with_mask('*');
+/// assert_eq!(abbrev.encode("Pineapple"), "xigak-*****-*****-bosek-sonax");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Abbreviation<'a> {
+    head: usize,
+    tail: usize,
+    elision: Elision<'a>,
+}
+
+impl Abbreviation<'static> {
+    /// Create an abbreviation that shows the first `head` and last `tail` words
+    /// of an encoding and elides the rest with `…`.
+    #[must_use]
+    pub const fn new(head: usize, tail: usize) -> Self {
+        Self {
+            head,
+            tail,
+            elision: Elision::Ellipsis("…"),
+        }
+    }
+}
+
+impl<'a> Abbreviation<'a> {
+    /// Elide hidden words with the given placeholder.
+    ///
+    /// [`boba::matches_abbreviated`](crate::matches_abbreviated) only
+    /// recognizes the `…` and `...` placeholders.
+    #[must_use]
+    pub const fn with_ellipsis(self, ellipsis: &str) -> Abbreviation<'_> {
+        Abbreviation {
+            head: self.head,
+            tail: self.tail,
+            elision: Elision::Ellipsis(ellipsis),
+        }
+    }
+
+    /// Mask every letter of the hidden words with the given character.
+    ///
+    /// The mask should not be `-` or a letter from the Bubble Babble alphabet,
+    /// otherwise the abbreviation cannot be checked with
+    /// [`boba::matches_abbreviated`](crate::matches_abbreviated).
+    #[must_use]
+    pub const fn with_mask(self, mask: char) -> Self {
+        Self {
+            head: self.head,
+            tail: self.tail,
+            elision: Elision::Mask(mask),
+        }
+    }
+
+    /// Return the number of leading words shown by this abbreviation.
+    #[must_use]
+    pub const fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Return the number of trailing words shown by this abbreviation.
+    #[must_use]
+    pub const fn tail(&self) -> usize {
+        self.tail
+    }
+
+    /// Return how hidden words are rendered by this abbreviation.
+    #[must_use]
+    pub const fn elision(&self) -> Elision<'a> {
+        self.elision
+    }
+
+    /// Encode a byte slice with the Bubble Babble encoding and abbreviate the
+    /// result.
+    #[must_use]
+    pub fn encode<T: AsRef<[u8]>>(&self, data: T) -> String {
+        self.abbreviate(crate::encode(data))
+    }
+
+    /// Abbreviate an existing Bubble Babble encoding.
+    ///
+    /// The input is split into words on `-` and is not otherwise validated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use boba::Abbreviation;
+    ///
+    /// let abbrev = Abbreviation::new(1, 1);
+    /// assert_eq!(
+    ///     abbrev.abbreviate("xesef-disof-gytuf-katof-movif-baxux"),
+    ///     "xesef-…-baxux"
+    /// );
+    /// assert_eq!(abbrev.abbreviate("xexax"), "xexax");
+    /// ```
+    #[must_use]
+    pub fn abbreviate<T: AsRef<str>>(&self, encoded: T) -> String {
+        let encoded = encoded.as_ref();
+        let count = encoded.split(SEPARATOR).count();
+        if self.head.saturating_add(self.tail) >= count {
+            return String::from(encoded);
+        }
+        let mut abbreviated = String::with_capacity(encoded.len());
+        let mut ellipsis = None;
+        for (index, word) in encoded.split(SEPARATOR).enumerate() {
+            if index >= self.head && index < count - self.tail {
+                match self.elision {
+                    Elision::Ellipsis(placeholder) => {
+                        if ellipsis.replace(placeholder).is_some() {
+                            continue;
+                        }
+                        push_word(&mut abbreviated, placeholder);
+                    }
+                    Elision::Mask(mask) => {
+                        if !abbreviated.is_empty() {
+                            abbreviated.push(SEPARATOR);
+                        }
+                        abbreviated.extend(word.chars().map(|_| mask));
+                    }
+                }
+            } else {
+                push_word(&mut abbreviated, word);
+            }
+        }
+        abbreviated
+    }
+}
+
+fn push_word(buf: &mut String, word: &str) {
+    if !buf.is_empty() {
+        buf.push(SEPARATOR);
+    }
+    buf.push_str(word);
+}
+
+/// Return whether `segment` is an ellipsis recognized by [`matches`].
+fn is_ellipsis(segment: &str) -> bool {
+    matches!(segment, "…" | "...")
+}
+
+/// Return whether `segment` masks `word` with one repeated character outside
+/// of the encoding alphabet.
+fn is_mask(segment: &str, word: &str) -> bool {
+    let mut chars = segment.chars();
+    let Some(mask) = chars.next() else {
+        return false;
+    };
+    let in_alphabet = u8::try_from(mask).is_ok_and(is_alphabet);
+    !in_alphabet && chars.all(|ch| ch == mask) && segment.chars().count() == word.len()
+}
+
+pub fn matches(abbrev: &str, data: &[u8]) -> bool {
+    let encoded = crate::encode::inner(data);
+    let words = encoded.split(SEPARATOR).collect::<Vec<_>>();
+    let segments = abbrev.split(SEPARATOR).collect::<Vec<_>>();
+
+    // An abbreviation must show at least the first and last words.
+    if segments.first() != words.first() || segments.last() != words.last() {
+        return false;
+    }
+
+    // An ellipsis stands in for one or more hidden words.
+    if let Some(pos) = segments.iter().position(|segment| is_ellipsis(segment)) {
+        let head = &segments[..pos];
+        let tail = &segments[pos + 1..];
+        return head.len() + tail.len() < words.len()
+            && words.starts_with(head)
+            && words.ends_with(tail);
+    }
+
+    // Otherwise, every hidden word is masked letter by letter.
+    segments.len() == words.len()
+        && segments
+            .iter()
+            .zip(&words)
+            .all(|(segment, word)| segment == word || is_mask(segment, word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abbreviate_ellipsis() {
+        let encoded = "xigak-nyryk-humil-bosek-sonax";
+        assert_eq!(
+            Abbreviation::new(2, 1).abbreviate(encoded),
+            "xigak-nyryk-…-sonax"
+        );
+        assert_eq!(Abbreviation::new(0, 1).abbreviate(encoded), "…-sonax");
+        assert_eq!(Abbreviation::new(1, 0).abbreviate(encoded), "xigak-…");
+        assert_eq!(Abbreviation::new(0, 0).abbreviate(encoded), "…");
+        assert_eq!(
+            Abbreviation::new(4, 0).abbreviate(encoded),
+            "xigak-nyryk-humil-bosek-…"
+        );
+    }
+
+    #[test]
+    fn test_abbreviate_mask() {
+        let encoded = "xigak-nyryk-humil-bosek-sonax";
+        let abbrev = Abbreviation::new(1, 2).with_mask('*');
+        assert_eq!(abbrev.abbreviate(encoded), "xigak-*****-*****-bosek-sonax");
+        let abbrev = Abbreviation::new(0, 0).with_mask('#');
+        assert_eq!(abbrev.abbreviate(encoded), "#####-#####-#####-#####-#####");
+    }
+
+    #[test]
+    fn test_abbreviate_short_encoding_is_unchanged() {
+        assert_eq!(Abbreviation::new(1, 1).abbreviate("xexax"), "xexax");
+        assert_eq!(
+            Abbreviation::new(1, 1).abbreviate("xebab-byxax"),
+            "xebab-byxax"
+        );
+        assert_eq!(
+            Abbreviation::new(usize::MAX, usize::MAX).abbreviate("xigak-nyryk-humil-bosek-sonax"),
+            "xigak-nyryk-humil-bosek-sonax"
+        );
+    }
+
+    #[test]
+    fn test_matches_ellipsis() {
+        assert!(matches("xigak-nyryk-…-sonax", b"Pineapple"));
+        assert!(matches("xigak-...-sonax", b"Pineapple"));
+        assert!(matches("xigak-nyryk-humil-…-sonax", b"Pineapple"));
+        assert!(!matches("xigak-nyryk-…-sonex", b"Pineapple"));
+        assert!(!matches("xigak-nyryk-…-sonax", b"Pinfapple"));
+        // the ellipsis must stand in for at least one word
+        assert!(!matches("xigak-nyryk-humil-…-bosek-sonax", b"Pineapple"));
+        // only one ellipsis is allowed
+        assert!(!matches("xigak-…-…-sonax", b"Pineapple"));
+        // only `…` and `...` are ellipses
+        assert!(!matches("xigak-~-sonax", b"Pineapple"));
+        assert!(!matches("xigak-..-sonax", b"Pineapple"));
+    }
+
+    #[test]
+    fn test_matches_requires_head_and_tail() {
+        assert!(!matches("…", b"Pineapple"));
+        assert!(!matches("...", b"Pineapple"));
+        assert!(!matches("…-sonax", b"Pineapple"));
+        assert!(!matches("xigak-…", b"Pineapple"));
+        assert!(!matches("xigak-nyryk-humil-bosek-…", b"Pineapple"));
+        assert!(!matches("*****-nyryk-humil-bosek-sonax", b"Pineapple"));
+        assert!(!matches("xigak-nyryk-humil-bosek-*****", b"Pineapple"));
+        assert!(!matches("#####-#####-#####-#####-#####", b"Pineapple"));
+    }
+
+    #[test]
+    fn test_matches_mask() {
+        assert!(matches("xigak-*****-*****-bosek-sonax", b"Pineapple"));
+        assert!(matches("xigak-#####-humil-#####-sonax", b"Pineapple"));
+        assert!(matches("xigak-nyryk-humil-bosek-sonax", b"Pineapple"));
+        assert!(!matches("xigak-*****-*****-bisik-sunux", b"Pineapple"));
+        assert!(matches("xigak-*****-*****-bisik-sunux", b"Pinfapple"));
+    }
+
+    #[test]
+    fn test_matches_rejects_invalid_masks() {
+        // masks must be as long as the word they hide
+        assert!(!matches("xigak-*-*-bosek-sonax", b"Pineapple"));
+        assert!(!matches("xigak-******-*****-bosek-sonax", b"Pineapple"));
+        // masks repeat a single character
+        assert!(!matches("xigak-**#**-*****-bosek-sonax", b"Pineapple"));
+        assert!(!matches("?-?-?-?-?", b"Pineapple"));
+        // a typo is not a placeholder
+        assert!(!matches("xigak-nyryk-humil-bosek-son4x", b"Pineapple"));
+        assert!(!matches("xigak-nyr4k-humil-bosek-sonax", b"Pineapple"));
+        assert!(!matches("xigak-nyryk-…-son4x", b"Pineapple"));
+    }
+
+    #[test]
+    fn test_matches_requires_placeholder_for_partial_form() {
+        assert!(!matches("xigak-sonax", b"Pineapple"));
+        assert!(!matches("", b"Pineapple"));
+        assert!(matches("xexax", b""));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let data = b"1234567890";
+        for head in 1..8 {
+            for tail in 1..8 {
+                let abbrev = Abbreviation::new(head, tail);
+                assert!(matches(&abbrev.encode(data), data));
+                assert!(matches(&abbrev.with_mask('*').encode(data), data));
+                assert!(matches(&abbrev.with_ellipsis("...").encode(data), data));
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The standard base64 alphabet.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Return the 6-bit value of a byte in the standard base64 alphabet.
+pub const fn sextet(byte: u8) -> Option<u8> {
+    match byte {
+        b'A'..=b'Z' => Some(byte - b'A'),
+        b'a'..=b'z' => Some(byte - b'a' + 26),
+        b'0'..=b'9' => Some(byte - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Write the standard base64 encoding of `data` with padding to `out`.
+pub fn encode<W: fmt::Write>(data: &[u8], out: &mut W) -> fmt::Result {
+    for chunk in data.chunks(3) {
+        for ch in encode_group(chunk) {
+            out.write_char(char::from(ch))?;
+        }
+    }
+    Ok(())
+}
+
+/// Return the 4 byte encoding of a group of 1 to 3 bytes, with padding.
+fn encode_group(chunk: &[u8]) -> [u8; 4] {
+    let mut group = [0; 4];
+    group[1..=chunk.len()].copy_from_slice(chunk);
+    let group = u32::from_be_bytes(group);
+    let mut encoded = [b'='; 4];
+    for (index, ch) in encoded.iter_mut().enumerate().take(chunk.len() + 1) {
+        *ch = ALPHABET[(group >> (18 - 6 * index)) as usize & 63];
+    }
+    encoded
+}
+
+/// A base64 encoder for data that arrives in chunks of any length.
+///
+/// Bytes that do not fill a 3 byte group are carried over to the next chunk,
+/// so the encoding is the same as encoding all the data at once.
+#[derive(Default, Debug)]
+pub struct ChunkEncoder {
+    carry: [u8; 3],
+    len: usize,
+}
+
+impl ChunkEncoder {
+    /// Append the encoding of the full groups of `data` to `out`.
+    pub fn update(&mut self, mut data: &[u8], out: &mut String) {
+        if self.len > 0 {
+            let take = data.len().min(3 - self.len);
+            self.carry[self.len..self.len + take].copy_from_slice(&data[..take]);
+            self.len += take;
+            data = &data[take..];
+            if self.len < 3 {
+                return;
+            }
+            push_group(&self.carry, out);
+            self.len = 0;
+        }
+        let (groups, rest) = data.split_at(data.len() - data.len() % 3);
+        for group in groups.chunks_exact(3) {
+            push_group(group, out);
+        }
+        self.carry[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+    }
+
+    /// Append the encoding of the carried bytes to `out` with padding.
+    pub fn finish(self, out: &mut String) {
+        if self.len > 0 {
+            push_group(&self.carry[..self.len], out);
+        }
+    }
+}
+
+fn push_group(chunk: &[u8], out: &mut String) {
+    out.extend(encode_group(chunk).map(char::from));
+}
+
+/// Decode standard base64 with optional padding.
+///
+/// On error, return the position of the first byte that cannot be part of a
+/// valid encoding, or the length of the input if it is truncated.
+pub fn decode(input: &[u8]) -> Result<Vec<u8>, usize> {
+    let data = match input {
+        [rest @ .., b'=', b'='] | [rest @ .., b'='] => rest,
+        _ => input,
+    };
+    if data.len() % 4 == 1 || (data.len() < input.len() && input.len() % 4 != 0) {
+        return Err(input.len());
+    }
+    let mut out = Vec::with_capacity(data.len() / 4 * 3 + 2);
+    for (index, chunk) in data.chunks(4).enumerate() {
+        let mut group = 0_u32;
+        for (offset, &byte) in chunk.iter().enumerate() {
+            let value = sextet(byte).ok_or(4 * index + offset)?;
+            group |= u32::from(value) << (18 - 6 * offset);
+        }
+        let bytes = group.to_be_bytes();
+        out.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let cases: [(&[u8], &str); 6] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"Pineapple", "UGluZWFwcGxl"),
+        ];
+        for (data, expected) in cases {
+            let mut out = String::new();
+            encode(data, &mut out).unwrap();
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn test_chunk_encoder() {
+        let data = b"Pineapple pizza";
+        for first in 0..data.len() {
+            for second in first..data.len() {
+                let mut encoder = ChunkEncoder::default();
+                let mut out = String::new();
+                encoder.update(&data[..first], &mut out);
+                encoder.update(&data[first..second], &mut out);
+                encoder.update(&data[second..], &mut out);
+                encoder.finish(&mut out);
+                assert_eq!(out, "UGluZWFwcGxlIHBpenph", "split {first} {second}");
+            }
+        }
+        let mut out = String::new();
+        let mut encoder = ChunkEncoder::default();
+        encoder.update(b"foob", &mut out);
+        encoder.finish(&mut out);
+        assert_eq!(out, "Zm9vYg==");
+    }
+
+    #[test]
+    fn test_decode() {
+        let cases: [(&[u8], &[u8]); 7] = [
+            (b"", b""),
+            (b"Zg==", b"f"),
+            (b"Zg", b"f"),
+            (b"Zm8=", b"fo"),
+            (b"Zm9v", b"foo"),
+            (b"Zm9vYg==", b"foob"),
+            (b"UGluZWFwcGxl", b"Pineapple"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(decode(input).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode(b"Zm9v!mFy"), Err(4));
+        assert_eq!(decode(b"Zm=v"), Err(2));
+        assert_eq!(decode(b"Zm9vY"), Err(5));
+        assert_eq!(decode(b"Zg="), Err(3));
+        assert_eq!(decode(b"="), Err(1));
+    }
+}
@@ -0,0 +1,412 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::decode::decode_append;
+use crate::encode::{ascii_to_str, encode_append, encoded_len};
+use crate::DecodeError;
+
+/// The Bubble Babble encodings of many inputs stored in one contiguous buffer.
+///
+/// Encoding many small inputs, like identifiers or digests, one at a time
+/// allocates a [`String`](alloc::string::String) for each of them. A batch
+/// amortizes those allocations by appending every encoding to a shared buffer
+/// and recording where each one ends.
+///
+/// This struct is created by [`boba::encode_batch`](crate::encode_batch()).
+/// Batches may also be built incrementally with [`push`](Self::push) and
+/// reused with [`clear`](Self::clear).
+///
+/// # Examples
+///
+/// ```
+/// use boba::EncodedBatch;
+///
+/// let mut batch = EncodedBatch::new();
+/// batch.push("Pineapple");
+/// batch.push("1234567890");
+///
+/// assert_eq!(batch.len(), 2);
+/// assert_eq!(batch.get(0), Some("xigak-nyryk-humil-bosek-sonax"));
+/// assert_eq!(batch.span(1), Some(29..64));
+/// assert_eq!(
+///     batch.as_bytes(),
+///     b"xigak-nyryk-humil-bosek-sonaxxesef-disof-gytuf-katof-movif-baxux"
+/// );
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EncodedBatch {
+    buf: Vec<u8>,
+    // The end of every encoding in `buf`. Each encoding starts at the end of
+    // the one before it.
+    ends: Vec<usize>,
+}
+
+impl EncodedBatch {
+    /// Create a new, empty batch.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            ends: Vec::new(),
+        }
+    }
+
+    /// Return the number of encodings in the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Return whether the batch has no encodings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Encode the given data and append the encoding to the batch.
+    pub fn push<T: AsRef<[u8]>>(&mut self, data: T) {
+        encode_append(data.as_ref(), &mut self.buf);
+        self.ends.push(self.buf.len());
+    }
+
+    /// Remove all encodings from the batch, keeping its allocated capacity.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.ends.clear();
+    }
+
+    /// Return the encoding at the given index.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let span = self.span(index)?;
+        self.buf.get(span).map(ascii_to_str)
+    }
+
+    /// Return the byte range of the encoding at the given index in
+    /// [`as_bytes`](Self::as_bytes).
+    #[must_use]
+    pub fn span(&self, index: usize) -> Option<Range<usize>> {
+        span(&self.ends, index)
+    }
+
+    /// Return the contiguous buffer holding every encoding in the batch.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Return an iterator over the encodings in the batch in order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        (0..self.len())
+            .map(|index| ascii_to_str(&self.buf[span(&self.ends, index).unwrap_or_default()]))
+    }
+}
+
+impl<T: AsRef<[u8]>> Extend<T> for EncodedBatch {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.ends.reserve(lower);
+        let Some(first) = iter.next() else {
+            return;
+        };
+        // Batched inputs are usually the same length, e.g. identifiers or
+        // digests, so size the buffer as if they were all like the first.
+        self.buf
+            .reserve(lower.saturating_mul(encoded_len(first.as_ref().len())));
+        self.push(first);
+        for data in iter {
+            self.push(data);
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<T> for EncodedBatch {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut batch = Self::new();
+        batch.extend(iter);
+        batch
+    }
+}
+
+/// The decoded bytes of many Bubble Babble encodings stored in one contiguous
+/// buffer.
+///
+/// Every input gets an entry in the batch. Inputs that fail to decode occupy
+/// an empty range in the buffer and record their [`DecodeError`].
+///
+/// This struct is created by [`boba::decode_batch`](crate::decode_batch()).
+/// Batches may also be built incrementally with [`push`](Self::push) and
+/// reused with [`clear`](Self::clear).
+///
+/// # Examples
+///
+/// ```
+/// use boba::{DecodeError, DecodedBatch};
+///
+/// let mut batch = DecodedBatch::new();
+/// assert_eq!(batch.push("xigak-nyryk-humil-bosek-sonax"), Ok(()));
+/// assert_eq!(batch.push("xexex"), Err(DecodeError::ChecksumMismatch));
+/// assert_eq!(batch.push("xebax"), Ok(()));
+///
+/// assert_eq!(batch.len(), 3);
+/// assert_eq!(batch.get(0), Some(Ok(&b"Pineapple"[..])));
+/// assert_eq!(batch.get(1), Some(Err(DecodeError::ChecksumMismatch)));
+/// assert_eq!(batch.get(2), Some(Ok(&[0][..])));
+/// assert_eq!(batch.errors(), [(1, DecodeError::ChecksumMismatch)]);
+/// assert_eq!(batch.as_bytes(), b"Pineapple\0");
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecodedBatch {
+    buf: Vec<u8>,
+    // The end of every decoded entry in `buf`. Each entry starts at the end of
+    // the one before it.
+    ends: Vec<usize>,
+    // The index and error of every entry that failed to decode, in order.
+    errors: Vec<(usize, DecodeError)>,
+}
+
+impl DecodedBatch {
+    /// Create a new, empty batch.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            ends: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Return the number of entries in the batch, including entries that
+    /// failed to decode.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Return whether the batch has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Decode the given Bubble Babble encoding and append the result to the
+    /// batch.
+    ///
+    /// An entry is added to the batch whether or not decoding succeeds.
+    ///
+    /// # Errors
+    ///
+    /// If `encoded` is not a valid Bubble Babble encoding, a [`DecodeError`] is
+    /// recorded for the entry and returned.
+    pub fn push<T: AsRef<[u8]>>(&mut self, encoded: T) -> Result<(), DecodeError> {
+        let result = decode_append(encoded.as_ref(), &mut self.buf);
+        if let Err(err) = result {
+            self.errors.push((self.ends.len(), err));
+        }
+        self.ends.push(self.buf.len());
+        result
+    }
+
+    /// Remove all entries from the batch, keeping its allocated capacity.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.ends.clear();
+        self.errors.clear();
+    }
+
+    /// Return the decoded bytes of the entry at the given index, or the error
+    /// encountered while decoding it.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<Result<&[u8], DecodeError>> {
+        let span = self.span(index)?;
+        if let Some(err) = self.error(index) {
+            return Some(Err(err));
+        }
+        self.buf.get(span).map(Ok)
+    }
+
+    /// Return the byte range of the entry at the given index in
+    /// [`as_bytes`](Self::as_bytes).
+    ///
+    /// Entries that failed to decode have an empty range.
+    #[must_use]
+    pub fn span(&self, index: usize) -> Option<Range<usize>> {
+        span(&self.ends, index)
+    }
+
+    /// Return the index and error of every entry that failed to decode, in
+    /// order.
+    #[must_use]
+    pub fn errors(&self) -> &[(usize, DecodeError)] {
+        &self.errors
+    }
+
+    /// Return the contiguous buffer holding the decoded bytes of every entry
+    /// in the batch.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Return an iterator over the decoded entries in the batch in order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<&[u8], DecodeError>> + '_ {
+        let mut errors = self.errors.iter().peekable();
+        (0..self.len()).map(
+            move |index| match errors.next_if(|&&(pos, _)| pos == index) {
+                Some(&(_, err)) => Err(err),
+                None => Ok(&self.buf[span(&self.ends, index).unwrap_or_default()]),
+            },
+        )
+    }
+
+    fn error(&self, index: usize) -> Option<DecodeError> {
+        let pos = self
+            .errors
+            .binary_search_by_key(&index, |&(pos, _)| pos)
+            .ok()?;
+        Some(self.errors[pos].1)
+    }
+}
+
+impl<T: AsRef<[u8]>> Extend<T> for DecodedBatch {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.ends.reserve(lower);
+        let Some(first) = iter.next() else {
+            return;
+        };
+        // Batched inputs are usually the same length, e.g. identifiers or
+        // digests, so size the buffer as if they were all like the first.
+        let first = first.as_ref();
+        let len = crate::decode::max_decoded_len(first.len().saturating_sub(2));
+        self.buf.reserve(lower.saturating_mul(len));
+        let _ = self.push(first);
+        for encoded in iter {
+            let _ = self.push(encoded);
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<T> for DecodedBatch {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut batch = Self::new();
+        batch.extend(iter);
+        batch
+    }
+}
+
+fn span(ends: &[usize], index: usize) -> Option<Range<usize>> {
+    let end = *ends.get(index)?;
+    let start = match index.checked_sub(1) {
+        Some(prev) => ends[prev],
+        None => 0,
+    };
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_encoded_batch_empty() {
+        let batch = EncodedBatch::new();
+        assert!(batch.is_empty());
+        assert_eq!(batch.get(0), None);
+        assert_eq!(batch.span(0), None);
+        assert_eq!(batch.iter().count(), 0);
+        assert_eq!(batch.as_bytes(), b"");
+    }
+
+    #[test]
+    fn test_encoded_batch_matches_encode() {
+        let inputs: [&[u8]; 5] = [b"", b"1234567890", b"Pineapple", &[0; 16], &[0xFF; 3]];
+        let batch = inputs.iter().collect::<EncodedBatch>();
+        assert_eq!(batch.len(), inputs.len());
+        for (index, input) in inputs.iter().enumerate() {
+            assert_eq!(batch.get(index), Some(crate::encode(input).as_str()));
+        }
+        let encoded = batch.iter().collect::<Vec<_>>();
+        let expected = inputs.iter().map(crate::encode).collect::<Vec<_>>();
+        assert_eq!(encoded, expected);
+        assert_eq!(batch.as_bytes(), expected.concat().as_bytes());
+    }
+
+    #[test]
+    fn test_encoded_batch_clear() {
+        let mut batch = ["Pineapple"].into_iter().collect::<EncodedBatch>();
+        batch.clear();
+        assert!(batch.is_empty());
+        batch.push("1234567890");
+        assert_eq!(batch.get(0), Some("xesef-disof-gytuf-katof-movif-baxux"));
+        assert_eq!(batch.span(0), Some(0..35));
+    }
+
+    #[test]
+    fn test_decoded_batch_per_item_errors() {
+        let batch = [
+            "xexax",
+            "yx",
+            "xigak-nyryk-humil-bosek-sonax",
+            "xesef-disof-gytuf-katof-movif-baxax",
+            "xebax",
+        ]
+        .into_iter()
+        .collect::<DecodedBatch>();
+        assert_eq!(batch.len(), 5);
+        assert_eq!(
+            batch.errors(),
+            [
+                (1, DecodeError::MalformedHeader),
+                (3, DecodeError::ChecksumMismatch)
+            ]
+        );
+        let expected: [Result<&[u8], DecodeError>; 5] = [
+            Ok(b""),
+            Err(DecodeError::MalformedHeader),
+            Ok(b"Pineapple"),
+            Err(DecodeError::ChecksumMismatch),
+            Ok(&[0]),
+        ];
+        assert_eq!(batch.iter().collect::<Vec<_>>(), expected);
+        for (index, result) in expected.into_iter().enumerate() {
+            assert_eq!(batch.get(index), Some(result));
+        }
+        assert_eq!(batch.get(5), None);
+        assert_eq!(batch.span(1), Some(0..0));
+        assert_eq!(batch.span(3), Some(9..9));
+        assert_eq!(batch.as_bytes(), b"Pineapple\0");
+    }
+
+    #[test]
+    fn test_decoded_batch_failed_item_leaves_buffer_unchanged() {
+        let mut batch = DecodedBatch::new();
+        assert_eq!(batch.push("xigak-nyryk-humil-bosek-sonax"), Ok(()));
+        // fails after decoding several words
+        assert_eq!(
+            batch.push("xigak-nyryk-humil-bosek-sonox"),
+            Err(DecodeError::Corrupted)
+        );
+        assert_eq!(batch.as_bytes(), b"Pineapple");
+        batch.clear();
+        assert!(batch.is_empty());
+        assert!(batch.errors().is_empty());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let inputs = (0..64_u8)
+            .map(|len| vec![len; usize::from(len)])
+            .collect::<Vec<_>>();
+        let encoded = inputs.iter().collect::<EncodedBatch>();
+        let decoded = encoded.iter().collect::<DecodedBatch>();
+        assert!(decoded.errors().is_empty());
+        let decoded = decoded.iter().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(decoded, inputs);
+    }
+}
@@ -0,0 +1,120 @@
+use alloc::vec;
+
+use bytes::{Buf, BufMut, Bytes};
+
+use crate::decode::decode_blocks;
+use crate::encode::{encoded_len, tail, write_pairs};
+use crate::{Checksum, DecodeError};
+
+/// Encode the remaining bytes of `data` chunk by chunk.
+///
+/// A pair of bytes may straddle two chunks, so the last byte of an odd length
+/// chunk is carried over to the next one along with the running checksum.
+pub fn encode<B: Buf>(mut data: B) -> Bytes {
+    let len = encoded_len(data.remaining());
+    let mut out = vec![0; len];
+    out[0] = b'x';
+    let mut pos = 1;
+    let mut checksum = Checksum::initial();
+    let mut pending = None;
+    while data.has_remaining() {
+        let chunk = data.chunk();
+        let advance = chunk.len();
+        let mut rest = chunk;
+        if let Some(left) = pending.take() {
+            let Some((&right, tail)) = rest.split_first() else {
+                break;
+            };
+            checksum = write_pairs(&[left, right], checksum, &mut out[pos..pos + 6]);
+            pos += 6;
+            rest = tail;
+        }
+        let (pairs, remainder) = rest.split_at(rest.len() & !1);
+        let words = &mut out[pos..pos + 3 * pairs.len()];
+        checksum = write_pairs(pairs, checksum, words);
+        pos += words.len();
+        pending = remainder.first().copied();
+        data.advance(advance);
+    }
+    out[pos..pos + 3].copy_from_slice(&tail(pending, checksum));
+    out[pos + 3] = b'x';
+    Bytes::from(out)
+}
+
+/// Decode `encoded` block by block and write the decoded bytes to `out`.
+pub fn decode_into<B: BufMut>(encoded: &[u8], out: &mut B) -> Result<usize, DecodeError> {
+    decode_blocks(encoded, |decoded| out.put_slice(decoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use bytes::{Buf, BytesMut};
+
+    use super::*;
+    use crate::decode::BLOCK_WORDS;
+    use crate::testing::data;
+
+    #[test]
+    fn test_encode_chains() {
+        let cases: [(&[u8], &[u8], &str); 4] = [
+            (b"", b"", "xexax"),
+            (b"P", b"ineapple", "xigak-nyryk-humil-bosek-sonax"),
+            (b"Pin", b"eapple", "xigak-nyryk-humil-bosek-sonax"),
+            (b"12345", b"67890", "xesef-disof-gytuf-katof-movif-baxux"),
+        ];
+        for (head, tail, expected) in cases {
+            assert_eq!(encode(head.chain(tail)), expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_encode_matches_encode_slice() {
+        let data = data(6 * BLOCK_WORDS + 3);
+        let mut out = vec![0; 6 * data.len()];
+        let len = crate::encode_slice(&data, &mut out).unwrap();
+        for split in [0, 1, 2, 3, 255, 256, data.len()] {
+            let (head, rest) = data.split_at(split);
+            let (mid, tail) = rest.split_at(rest.len() / 3);
+            let chain = head.chain(mid).chain(Bytes::copy_from_slice(tail));
+            assert_eq!(encode(chain), out[..len], "split {split}");
+        }
+    }
+
+    #[test]
+    fn test_decode_into() {
+        let data = data(6 * BLOCK_WORDS + 3);
+        for len in [0, 1, 2, 2 * BLOCK_WORDS, 2 * BLOCK_WORDS + 1, data.len()] {
+            let data = &data[..len];
+            let encoded = encode(data);
+            let mut out = BytesMut::from(&b"prefix"[..]);
+            assert_eq!(decode_into(&encoded, &mut out), Ok(len));
+            assert_eq!(out[..6], *b"prefix");
+            assert_eq!(out[6..], *data);
+        }
+    }
+
+    #[test]
+    fn test_decode_into_errors_match_decode() {
+        let cases: [&[u8]; 6] = [
+            b"",
+            b"x",
+            b"xigak-nyryk-humil-bosek-sonex",
+            b"xesef-disof-gytuf-katof-movif-baxix",
+            b"xigak-nyr!k-humil-bosek-sonax",
+            b"xigak-nyrykhumil-bosek-sonax",
+        ];
+        for encoded in cases {
+            let mut out = Vec::new();
+            let result = decode_into(encoded, &mut out);
+            match crate::decode(encoded) {
+                Ok(decoded) => {
+                    assert_eq!(result, Ok(decoded.len()));
+                    assert_eq!(out, decoded);
+                }
+                Err(err) => assert_eq!(result, Err(err)),
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+/// The number of checksum states.
+const STATES: u8 = 36;
+
+// For every checksum state `c` and residue `t`, the next checksum state
+// `(5 * c + t) % 36`.
+//
+// The checksum recurrence `(5 * c + 7 * left + right) % 36` is the serial
+// dependency of the encoder. Reducing `7 * left + right` does not depend on
+// the previous state, which leaves a single table lookup per pair on the
+// critical path.
+//
+// The table has a row for every `u8` so lookups need no bounds check. Rows
+// past 35 are never used.
+const NEXT: [[u8; 36]; 256] = {
+    let mut table = [[0_u8; 36]; 256];
+    let mut checksum = 0;
+    while checksum < 256 {
+        let mut residue = 0;
+        while residue < 36 {
+            table[checksum][residue] = ((5 * checksum + residue) % 36) as u8;
+            residue += 1;
+        }
+        checksum += 1;
+    }
+    table
+};
+
+/// The running checksum of a Bubble Babble encoding.
+///
+/// The checksum starts at 1 and is updated with every pair of encoded bytes
+/// by the recurrence `C' = (5 * C + 7 * left + right) % 36`. Its state is
+/// mixed into the vowels of every word and encoded directly in the final word
+/// of encodings of an even number of bytes.
+///
+/// The recurrence is affine, so the effect of any run of pairs on the
+/// checksum can be captured in a [`ChecksumMap`] and applied to a state
+/// without walking the data again. See [`Checksum::combine`] and
+/// [`Checksum::advance_by`].
+///
+/// # Examples
+///
+/// ```
+/// use boba::Checksum;
+///
+/// let checksum = Checksum::initial().update(*b"12").update(*b"34");
+/// assert_eq!(checksum, Checksum::from_bytes(b"1234"));
+/// assert_eq!(Checksum::from_bytes(b"1234567890").value(), 24);
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Checksum(u8);
+
+impl Checksum {
+    /// Return the checksum state at the start of every encoding.
+    #[must_use]
+    pub const fn initial() -> Self {
+        Self(1)
+    }
+
+    /// Create a checksum state from its value.
+    ///
+    /// Returns `None` if `value` is not a checksum state, i.e. it is not less
+    /// than 36.
+    #[must_use]
+    pub const fn from_value(value: u8) -> Option<Self> {
+        if value < STATES {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    /// Return the value of this checksum state, which is less than 36.
+    #[must_use]
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Return the checksum state after encoding the given pair of bytes.
+    #[must_use]
+    #[inline]
+    pub const fn update(self, pair: [u8; 2]) -> Self {
+        let [left, right] = pair;
+        let value = (5 * self.0 as u16 + 7 * left as u16 + right as u16) % 36;
+        Self(value as u8)
+    }
+
+    /// Return the checksum state after encoding the given pair of bytes, like
+    /// [`update`](Self::update), with a table lookup.
+    ///
+    /// This is faster when the checksum update is the only serial dependency
+    /// in a loop, as in the encoder. The parallel decoder has other work to
+    /// overlap with the arithmetic and is faster with `update`.
+    #[inline]
+    pub(crate) const fn update_by_table(self, pair: [u8; 2]) -> Self {
+        let [left, right] = pair;
+        let residue = (7 * left as usize + right as usize) % 36;
+        Self(NEXT[self.0 as usize][residue])
+    }
+
+    /// Return the checksum state after encoding the pairs of bytes in `data`.
+    ///
+    /// The final byte of odd length data is not part of a pair and does not
+    /// affect the checksum.
+    #[must_use]
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self::initial().update_bytes(data)
+    }
+
+    /// Return the checksum state after encoding the pairs of bytes in `data`
+    /// starting from this state.
+    ///
+    /// The final byte of odd length data is not part of a pair and does not
+    /// affect the checksum. The data is walked pair by pair; to jump ahead
+    /// over runs of pairs without walking them, see
+    /// [`advance_by`](Self::advance_by).
+    #[must_use]
+    pub fn update_bytes(self, data: &[u8]) -> Self {
+        data.chunks_exact(2)
+            .fold(self, |checksum, pair| checksum.update([pair[0], pair[1]]))
+    }
+
+    /// Return the checksum state after encoding a run of pairs, given the
+    /// state before the run and the [`ChecksumMap`] of the run.
+    ///
+    /// This is how checksums of independently processed chunks of data are
+    /// stitched together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use boba::{Checksum, ChecksumMap};
+    ///
+    /// let data = b"Pineapple pizza";
+    /// let (head, tail) = data.split_at(6);
+    ///
+    /// // `head` and `tail` can be processed independently.
+    /// let prefix = Checksum::from_bytes(head);
+    /// let suffix = ChecksumMap::from_bytes(tail);
+    ///
+    /// assert_eq!(Checksum::combine(prefix, suffix), Checksum::from_bytes(data));
+    /// ```
+    #[must_use]
+    pub const fn combine(prefix: Self, suffix: ChecksumMap) -> Self {
+        suffix.apply(prefix)
+    }
+
+    /// Return the checksum state after encoding `count` runs of pairs in a
+    /// row starting from this state, given the [`ChecksumMap`] of the run.
+    ///
+    /// This takes time logarithmic in `count` and does not touch the data,
+    /// so the checksum state at any offset into data that repeats, or whose
+    /// map is known, is found without walking the data up to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use boba::{Checksum, ChecksumMap};
+    ///
+    /// let zeros = vec![0_u8; 1 << 20];
+    /// let pairs = ChecksumMap::pair([0, 0]);
+    ///
+    /// let checksum = Checksum::initial().advance_by(pairs, zeros.len() / 2);
+    /// assert_eq!(checksum, Checksum::from_bytes(&zeros));
+    /// ```
+    #[must_use]
+    pub const fn advance_by(self, map: ChecksumMap, count: usize) -> Self {
+        map.repeat(count).apply(self)
+    }
+}
+
+impl Default for Checksum {
+    fn default() -> Self {
+        Self::initial()
+    }
+}
+
+impl From<Checksum> for u8 {
+    fn from(checksum: Checksum) -> Self {
+        checksum.value()
+    }
+}
+
+/// The effect of encoding a run of byte pairs on a [`Checksum`].
+///
+/// Every map is an affine function `C' = (a * C + b) % 36` of the checksum
+/// state. Maps compose with [`then`](Self::then), so the map of a long run of
+/// data can be assembled from the maps of its chunks, and a map can be
+/// [repeated](Self::repeat) to jump ahead over the same data many times in
+/// logarithmic time.
+///
+/// # Examples
+///
+/// ```
+/// use boba::{Checksum, ChecksumMap};
+///
+/// let zeros = [0_u8; 1024];
+/// let map = ChecksumMap::from_bytes(&zeros[..2]).repeat(512);
+/// assert_eq!(map, ChecksumMap::from_bytes(&zeros));
+/// assert_eq!(map.apply(Checksum::initial()), Checksum::from_bytes(&zeros));
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ChecksumMap {
+    mul: u8,
+    add: u8,
+}
+
+impl ChecksumMap {
+    /// Return the map of an empty run, which leaves every checksum state
+    /// unchanged.
+    #[must_use]
+    pub const fn identity() -> Self {
+        Self { mul: 1, add: 0 }
+    }
+
+    /// Return the map of encoding a single pair of bytes.
+    #[must_use]
+    pub const fn pair(pair: [u8; 2]) -> Self {
+        let [left, right] = pair;
+        Self {
+            mul: 5,
+            add: ((7 * left as u16 + right as u16) % 36) as u8,
+        }
+    }
+
+    /// Return the map of encoding the pairs of bytes in `data`.
+    ///
+    /// The final byte of odd length data is not part of a pair and does not
+    /// affect the checksum.
+    #[must_use]
+    pub fn from_bytes(data: &[u8]) -> Self {
+        // Every pair multiplies the state by 5, and `5^6 % 36 == 1`. The
+        // offset is the image of the zero state.
+        let mul = match (data.len() / 2) % 6 {
+            0 => 1,
+            1 => 5,
+            2 => 25,
+            3 => 17,
+            4 => 13,
+            _ => 29,
+        };
+        let add = data.chunks_exact(2).fold(Checksum(0), |checksum, pair| {
+            checksum.update_by_table([pair[0], pair[1]])
+        });
+        Self { mul, add: add.0 }
+    }
+
+    /// Return the map that applies this map and then `next`.
+    #[must_use]
+    pub const fn then(self, next: Self) -> Self {
+        let mul = (next.mul as u16 * self.mul as u16) % 36;
+        let add = (next.mul as u16 * self.add as u16 + next.add as u16) % 36;
+        Self {
+            mul: mul as u8,
+            add: add as u8,
+        }
+    }
+
+    /// Return the map that applies this map `count` times in a row.
+    #[must_use]
+    pub const fn repeat(self, mut count: usize) -> Self {
+        let mut result = Self::identity();
+        let mut base = self;
+        while count > 0 {
+            if count & 1 == 1 {
+                result = result.then(base);
+            }
+            base = base.then(base);
+            count >>= 1;
+        }
+        result
+    }
+
+    /// Return the checksum state after applying this map to `checksum`.
+    #[must_use]
+    pub const fn apply(self, checksum: Checksum) -> Checksum {
+        let value = (self.mul as u16 * checksum.0 as u16 + self.add as u16) % 36;
+        Checksum(value as u8)
+    }
+}
+
+impl Default for ChecksumMap {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recurrence(checksum: u8, left: u8, right: u8) -> u8 {
+        ((u16::from(checksum) * 5 + u16::from(left) * 7 + u16::from(right)) % 36) as u8
+    }
+
+    #[test]
+    fn test_update_matches_recurrence() {
+        for value in 0..36 {
+            let checksum = Checksum::from_value(value).unwrap();
+            for left in 0..=u8::MAX {
+                for right in 0..=u8::MAX {
+                    let expected = recurrence(value, left, right);
+                    assert_eq!(checksum.update([left, right]).value(), expected);
+                    assert_eq!(checksum.update_by_table([left, right]).value(), expected);
+                    assert_eq!(
+                        ChecksumMap::pair([left, right]).apply(checksum).value(),
+                        expected
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_value() {
+        assert_eq!(Checksum::from_value(0), Some(Checksum(0)));
+        assert_eq!(Checksum::from_value(35), Some(Checksum(35)));
+        assert_eq!(Checksum::from_value(36), None);
+        assert_eq!(Checksum::default(), Checksum::initial());
+        assert_eq!(u8::from(Checksum::initial()), 1);
+    }
+
+    #[test]
+    fn test_odd_byte_is_ignored() {
+        assert_eq!(
+            Checksum::from_bytes(b"Pineapple"),
+            Checksum::from_bytes(b"Pineappl")
+        );
+        assert_eq!(
+            ChecksumMap::from_bytes(b"Pineapple"),
+            ChecksumMap::from_bytes(b"Pineappl")
+        );
+        assert_eq!(Checksum::from_bytes(b"x"), Checksum::initial());
+    }
+
+    #[test]
+    fn test_combine_at_every_split() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let expected = Checksum::from_bytes(data);
+        for split in (0..=data.len()).step_by(2) {
+            let (head, tail) = data.split_at(split);
+            let combined =
+                Checksum::combine(Checksum::from_bytes(head), ChecksumMap::from_bytes(tail));
+            assert_eq!(combined, expected, "split at {split}");
+            assert_eq!(Checksum::from_bytes(head).update_bytes(tail), expected);
+        }
+    }
+
+    #[test]
+    fn test_advance_by_matches_update() {
+        let data = b"Pineapple pizza!";
+        let map = ChecksumMap::from_bytes(data);
+        let mut expected = Checksum::initial();
+        for count in 0..80 {
+            assert_eq!(Checksum::initial().advance_by(map, count), expected);
+            expected = expected.update_bytes(data);
+        }
+        let pair = ChecksumMap::pair(*b"ab");
+        assert_eq!(
+            Checksum::initial().advance_by(pair, usize::MAX),
+            Checksum::initial()
+                .advance_by(pair, usize::MAX - 1)
+                .update(*b"ab")
+        );
+    }
+
+    #[test]
+    fn test_then_is_associative() {
+        let a = ChecksumMap::from_bytes(b"ab");
+        let b = ChecksumMap::from_bytes(b"cdef");
+        let c = ChecksumMap::from_bytes(b"\xFF\x00");
+        assert_eq!(a.then(b).then(c), a.then(b.then(c)));
+        assert_eq!(a.then(ChecksumMap::identity()), a);
+        assert_eq!(ChecksumMap::identity().then(a), a);
+        assert_eq!(ChecksumMap::default(), ChecksumMap::identity());
+    }
+
+    #[test]
+    fn test_from_bytes_matches_composition() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let mut expected = ChecksumMap::identity();
+        for (pairs, pair) in data.chunks_exact(2).enumerate() {
+            assert_eq!(ChecksumMap::from_bytes(&data[..2 * pairs]), expected);
+            expected = expected.then(ChecksumMap::pair([pair[0], pair[1]]));
+        }
+    }
+
+    #[test]
+    fn test_repeat() {
+        let map = ChecksumMap::from_bytes(b"Pine");
+        let mut expected = ChecksumMap::identity();
+        for count in 0..100 {
+            assert_eq!(map.repeat(count), expected);
+            expected = expected.then(map);
+        }
+    }
+}
@@ -0,0 +1,368 @@
+use core::{cmp, fmt};
+use std::io;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::encode::encode_into;
+use crate::{buf, encoded_len, max_decoded_len, DecodeError};
+
+/// A [`tokio-util`] codec for newline-delimited Bubble Babble frames.
+///
+/// Each line is one frame holding the encoding of one message. The decoder
+/// splits its input on `\n`, strips a trailing `\r`, and yields the decoded
+/// data of each frame as [`Bytes`]. The encoder writes the encoding of each
+/// message followed by `\n`.
+///
+/// A codec created with [`new_with_max_length`](Self::new_with_max_length)
+/// limits the length of encoded frames, not including the newline or a `\r`
+/// directly before it. Longer
+/// frames are rejected before they are decoded, and the decoder discards
+/// their input up to the next newline. The default is no limit, which lets a
+/// peer that never sends a newline make the decoder buffer without bound.
+///
+/// A frame that fails to decode is consumed before the error is returned, so
+/// the decoder can resume at the next frame.
+///
+/// [`tokio-util`]: https://docs.rs/tokio-util
+///
+/// # Examples
+///
+/// ```
+/// use boba::BubbleBabbleCodec;
+/// use bytes::BytesMut;
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// # fn example() -> Result<(), boba::BubbleBabbleCodecError> {
+/// let mut codec = BubbleBabbleCodec::new_with_max_length(64);
+/// let mut buf = BytesMut::new();
+/// codec.encode("Pineapple", &mut buf)?;
+/// assert_eq!(buf, "xigak-nyryk-humil-bosek-sonax\n");
+///
+/// buf.extend_from_slice(b"xesef-disof-gytuf-katof-movif-baxux\r\nxexax");
+/// assert_eq!(codec.decode(&mut buf)?.unwrap(), "Pineapple");
+/// assert_eq!(codec.decode(&mut buf)?.unwrap(), "1234567890");
+/// assert_eq!(codec.decode(&mut buf)?, None);
+/// assert_eq!(codec.decode_eof(&mut buf)?.unwrap(), "");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BubbleBabbleCodec {
+    max_length: usize,
+    // The index in the buffer to resume searching for a newline at.
+    next_index: usize,
+    // Whether the rest of an oversized frame is being discarded.
+    is_discarding: bool,
+}
+
+impl BubbleBabbleCodec {
+    /// Create a codec for frames of any length.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::new_with_max_length(usize::MAX)
+    }
+
+    /// Create a codec for frames with encodings of at most `max_length`
+    /// bytes.
+    ///
+    /// Messages with encodings longer than `max_length` bytes are rejected
+    /// with [`BubbleBabbleCodecError::MaxFrameLengthExceeded`] by both the
+    /// encoder and the decoder.
+    #[must_use]
+    pub const fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            max_length,
+            next_index: 0,
+            is_discarding: false,
+        }
+    }
+
+    /// Return the maximum length of the encoded frames of this codec.
+    #[must_use]
+    pub const fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl Default for BubbleBabbleCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn strip_cr(frame: &[u8]) -> &[u8] {
+    frame.strip_suffix(b"\r").unwrap_or(frame)
+}
+
+fn decode_frame(frame: &[u8]) -> Result<Bytes, DecodeError> {
+    let frame = strip_cr(frame);
+    let mut out = BytesMut::with_capacity(max_decoded_len(frame.len()));
+    buf::decode_into(frame, &mut out)?;
+    Ok(out.freeze())
+}
+
+impl Decoder for BubbleBabbleCodec {
+    type Item = Bytes;
+    type Error = BubbleBabbleCodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, Self::Error> {
+        loop {
+            // Search two bytes past the maximum length so the `\r\n` of a
+            // frame of exactly the maximum length is found.
+            let read_to = cmp::min(self.max_length.saturating_add(2), src.len());
+            let newline = src[self.next_index..read_to]
+                .iter()
+                .position(|&byte| byte == b'\n')
+                .map(|offset| self.next_index + offset);
+            match (self.is_discarding, newline) {
+                (true, Some(pos)) => {
+                    let _ = src.split_to(pos + 1);
+                    self.is_discarding = false;
+                    self.next_index = 0;
+                }
+                (true, None) => {
+                    let _ = src.split_to(read_to);
+                    self.next_index = 0;
+                    if src.is_empty() {
+                        return Ok(None);
+                    }
+                }
+                (false, Some(pos)) => {
+                    self.next_index = 0;
+                    let frame = src.split_to(pos + 1);
+                    if strip_cr(&frame[..pos]).len() > self.max_length {
+                        return Err(BubbleBabbleCodecError::MaxFrameLengthExceeded);
+                    }
+                    return Ok(Some(decode_frame(&frame[..pos])?));
+                }
+                (false, None) if strip_cr(src).len() > self.max_length => {
+                    self.is_discarding = true;
+                    return Err(BubbleBabbleCodecError::MaxFrameLengthExceeded);
+                }
+                (false, None) => {
+                    self.next_index = read_to;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, Self::Error> {
+        if let Some(frame) = self.decode(src)? {
+            return Ok(Some(frame));
+        }
+        self.next_index = 0;
+        // The last frame may not end with a newline.
+        if src.is_empty() || src[..] == *b"\r" {
+            src.clear();
+            return Ok(None);
+        }
+        let frame = src.split();
+        Ok(Some(decode_frame(&frame)?))
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for BubbleBabbleCodec {
+    type Error = BubbleBabbleCodecError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let data = item.as_ref();
+        let len = match encoded_len(data.len()) {
+            Some(len) if len <= self.max_length => len,
+            _ => return Err(BubbleBabbleCodecError::MaxFrameLengthExceeded),
+        };
+        let start = dst.len();
+        dst.reserve(len + 1);
+        dst.resize(start + len, 0);
+        encode_into(data, &mut dst[start..]);
+        dst.put_u8(b'\n');
+        Ok(())
+    }
+}
+
+/// An error returned by [`BubbleBabbleCodec`].
+#[derive(Debug)]
+pub enum BubbleBabbleCodecError {
+    /// The encoding of a frame is longer than the maximum length of the
+    /// codec.
+    MaxFrameLengthExceeded,
+    /// A frame is not a valid Bubble Babble encoding.
+    Decode(DecodeError),
+    /// Reading or writing the underlying I/O stream failed.
+    Io(io::Error),
+}
+
+impl From<DecodeError> for BubbleBabbleCodecError {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<io::Error> for BubbleBabbleCodecError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl fmt::Display for BubbleBabbleCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxFrameLengthExceeded => f.write_str("Maximum frame length exceeded"),
+            Self::Decode(err) => err.fmt(f),
+            Self::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for BubbleBabbleCodecError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::MaxFrameLengthExceeded => None,
+            Self::Decode(err) => Some(err),
+            Self::Io(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn decode_all(
+        codec: &mut BubbleBabbleCodec,
+        src: &mut BytesMut,
+    ) -> Vec<Result<Bytes, BubbleBabbleCodecError>> {
+        let mut frames = Vec::new();
+        loop {
+            match codec.decode_eof(src) {
+                Ok(Some(frame)) => frames.push(Ok(frame)),
+                Ok(None) => return frames,
+                Err(err) => frames.push(Err(err)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_frames() {
+        let mut codec = BubbleBabbleCodec::new();
+        let mut src = BytesMut::from(&b"xexax\nxigak-nyryk-humil-bosek-sonax\r\nxesef"[..]);
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), "");
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), "Pineapple");
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        src.extend_from_slice(b"-disof-gytuf-katof-movif-baxux");
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        src.extend_from_slice(b"\n");
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), "1234567890");
+        assert!(src.is_empty());
+        assert_eq!(codec.decode_eof(&mut src).unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_eof_unterminated_frame() {
+        let mut codec = BubbleBabbleCodec::new();
+        let mut src = BytesMut::from(&b"xexax\nxigak-nyryk-humil-bosek-sonax\r"[..]);
+        let frames = decode_all(&mut codec, &mut src);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(*frames[0].as_ref().unwrap(), "");
+        assert_eq!(*frames[1].as_ref().unwrap(), "Pineapple");
+
+        let mut src = BytesMut::from(&b"xexax\n\r"[..]);
+        assert_eq!(decode_all(&mut codec, &mut src).len(), 1);
+    }
+
+    #[test]
+    fn test_decode_error_skips_frame() {
+        let mut codec = BubbleBabbleCodec::new();
+        let mut src = BytesMut::from(&b"xesef-disof-gytuf-katof-movif-baxix\n\nxexax\n"[..]);
+        let frames = decode_all(&mut codec, &mut src);
+        assert!(matches!(
+            frames[0],
+            Err(BubbleBabbleCodecError::Decode(
+                DecodeError::ChecksumMismatch
+            ))
+        ));
+        assert!(matches!(frames[1], Err(BubbleBabbleCodecError::Decode(_))));
+        assert_eq!(*frames[2].as_ref().unwrap(), "");
+        assert_eq!(frames.len(), 3);
+    }
+
+    #[test]
+    fn test_decode_max_length() {
+        // The `\r` of a `\r\n` newline does not count toward the limit.
+        let mut codec = BubbleBabbleCodec::new_with_max_length(29);
+        let mut src = BytesMut::from(&b"xigak-nyryk-humil-bosek-sonax\r"[..]);
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        src.extend_from_slice(b"\nxigak-nyryk-humil-bosek-sonax\n");
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), "Pineapple");
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), "Pineapple");
+        assert!(src.is_empty());
+
+        let mut codec = BubbleBabbleCodec::new_with_max_length(28);
+        let mut src = BytesMut::from(&b"xigak-nyryk-humil-bosek-sonax\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(BubbleBabbleCodecError::MaxFrameLengthExceeded)
+        ));
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        assert!(src.is_empty());
+
+        // A frame one byte too long is rejected when its newline is found.
+        let mut src = BytesMut::from(&b"xigak-nyryk-humil-bosek-sonax\nxexax\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(BubbleBabbleCodecError::MaxFrameLengthExceeded)
+        ));
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), "");
+
+        let mut codec = BubbleBabbleCodec::new_with_max_length(29);
+        let mut src = BytesMut::from(&b"xigak-nyryk-humil-bosek-sonaxx\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(BubbleBabbleCodecError::MaxFrameLengthExceeded)
+        ));
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        assert!(src.is_empty());
+
+        // The oversized frame is rejected before its newline arrives, and is
+        // discarded across reads.
+        let mut src = BytesMut::from(&b"xesef-disof-gytuf-katof"[..]);
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        src.extend_from_slice(b"-movif-baxux");
+        assert!(codec.decode(&mut src).is_err());
+        assert_eq!(codec.decode(&mut src).unwrap(), None);
+        src.extend_from_slice(b"\nxigak-nyryk-humil-bosek-sonax\nxexax");
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap(), "Pineapple");
+        assert_eq!(codec.decode_eof(&mut src).unwrap().unwrap(), "");
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut codec = BubbleBabbleCodec::new_with_max_length(29);
+        let mut dst = BytesMut::from(&b"xexax\n"[..]);
+        codec.encode(b"Pineapple", &mut dst).unwrap();
+        codec.encode(Vec::new(), &mut dst).unwrap();
+        assert_eq!(dst, "xexax\nxigak-nyryk-humil-bosek-sonax\nxexax\n");
+        assert!(matches!(
+            codec.encode("1234567890", &mut dst),
+            Err(BubbleBabbleCodecError::MaxFrameLengthExceeded)
+        ));
+
+        let frames = decode_all(&mut codec, &mut dst);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(*frames[1].as_ref().unwrap(), "Pineapple");
+    }
+
+    #[test]
+    fn test_error_source() {
+        let err = BubbleBabbleCodecError::from(DecodeError::Corrupted);
+        assert_eq!(err.to_string(), "Corrupted input");
+        assert!(core::error::Error::source(&err).is_some());
+        let err = BubbleBabbleCodecError::MaxFrameLengthExceeded;
+        assert!(core::error::Error::source(&err).is_none());
+    }
+}
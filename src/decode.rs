@@ -1,145 +1,626 @@
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
-use crate::DecodeError;
+use crate::{Checksum, DecodeError, DecodeSliceError};
 
 const HEADER: u8 = b'x';
 const TRAILER: u8 = b'x';
 
-// The alphabet as a byte slice.
-const ALPHABET: &[u8] = b"aeiouybcdfghklmnprstvzx-";
+const VOWELS: &[u8; 6] = b"aeiouy";
+const CONSONANTS: &[u8; 16] = b"bcdfghklmnprstvz";
+
+// Byte classes stored in the high bits of `DECODE_TABLE` entries. The low bits
+// hold the index of the byte in its vowel or consonant table.
+const VOWEL: u8 = 0x10;
+const CONSONANT: u8 = 0x20;
+const MID: u8 = 0x40;
+const SEPARATOR: u8 = 0x80;
+const INDEX_MASK: u8 = 0x0F;
+
+// Marks a vowel that is inconsistent with the checksum in `HIGH_BITS` and
+// `LOW_BITS`.
+const INVALID_BITS: u8 = 0xFF;
 
 // A const block that computes an array of 256 u8's.
 //
-// For every byte in the ASCII table, the corresponding entry is 1
-// if that byte is in ALPHABET, and 0 otherwise.
-const ALPHABET_TABLE: [u8; 256] = {
+// For every byte in the ASCII table, the corresponding entry is 0 if the byte
+// is not in the 24 character encoding alphabet. Otherwise, the entry holds
+// the class of the byte and, for vowels and consonants, its index.
+const DECODE_TABLE: [u8; 256] = {
     let mut table = [0_u8; 256];
     let mut i = 0;
-    while i < ALPHABET.len() {
-        table[ALPHABET[i] as usize] = 1;
+    while i < VOWELS.len() {
+        table[VOWELS[i] as usize] = VOWEL | i as u8;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < CONSONANTS.len() {
+        table[CONSONANTS[i] as usize] = CONSONANT | i as u8;
         i += 1;
     }
+    table[b'x' as usize] = MID;
+    table[b'-' as usize] = SEPARATOR;
+    table
+};
+
+// For every checksum state and vowel index, the high two bits of the byte
+// encoded by a vowel in the first position of a 3-tuple.
+//
+// Entries for vowels that are inconsistent with the checksum are
+// `INVALID_BITS`.
+const HIGH_BITS: [[u8; 6]; 36] = {
+    let mut table = [[INVALID_BITS; 6]; 36];
+    let mut checksum = 0;
+    while checksum < 36 {
+        let mut bits = 0;
+        while bits < 4 {
+            table[checksum][(bits + checksum) % 6] = bits as u8;
+            bits += 1;
+        }
+        checksum += 1;
+    }
+    table
+};
+
+// For every checksum state and vowel index, the low two bits of the byte
+// encoded by a vowel in the last position of a 3-tuple.
+//
+// Entries for vowels that are inconsistent with the checksum are
+// `INVALID_BITS`.
+const LOW_BITS: [[u8; 6]; 36] = {
+    let mut table = [[INVALID_BITS; 6]; 36];
+    let mut checksum = 0;
+    while checksum < 36 {
+        let mut bits = 0;
+        while bits < 4 {
+            table[checksum][(bits + checksum / 6) % 6] = bits as u8;
+            bits += 1;
+        }
+        checksum += 1;
+    }
+    table
+};
+
+// Fields of the sums of `WORD_TABLE` entries over the five letters of a word.
+//
+// The sum holds the offset of the vowels in a row of `STEP` in bits 0..6,
+// the bits of the first byte of the pair carried by the middle consonant and
+// the second byte next to them in bits 8..24, and the shares of the three
+// consonants in the next checksum state scaled by 36 in bits 24..36. Every
+// field is wide enough for the sum of its parts, so the additions never carry
+// between fields.
+const VOWELS_MASK: u64 = 0x3F;
+const PAIR_SHIFT: u32 = 8;
+const SHARE_SHIFT: u32 = 24;
+const SHARE_MASK: u64 = 0xFFF;
+// Set in the sum if any letter is out of place.
+const INVALID_LETTER: u64 = 1 << 40;
+const INVALID_WORD: u64 = !((1 << 40) - 1);
+
+// For every position of a letter in a word and every byte, the contribution
+// of the byte to the word sum, or `INVALID_LETTER` if the byte is not a
+// vowel or consonant as required at that position.
+//
+// The next checksum state after a word is `(5 * c + 7 * byte1 + byte2) % 36`.
+// The share of the consonants, `7 * (mid << 2) + (up << 4) + down`, does not
+// depend on the previous state, so it is summed up here, reduced letter by
+// letter, and leaves a single lookup in `STEP` and an add on the critical
+// path of the decoder.
+const WORD_TABLE: [[u64; 256]; 5] = {
+    let mut table = [[INVALID_LETTER; 256]; 5];
+    let mut i = 0;
+    while i < VOWELS.len() {
+        let vowel = VOWELS[i] as usize;
+        table[0][vowel] = 6 * i as u64;
+        table[2][vowel] = i as u64;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < CONSONANTS.len() {
+        let consonant = CONSONANTS[i] as usize;
+        let index = i as u64;
+        table[1][consonant] = (index << (PAIR_SHIFT + 2)) | (36 * (28 * index % 36)) << SHARE_SHIFT;
+        table[3][consonant] =
+            (index << (PAIR_SHIFT + 12)) | (36 * (16 * index % 36)) << SHARE_SHIFT;
+        table[4][consonant] = (index << (PAIR_SHIFT + 8)) | (36 * index) << SHARE_SHIFT;
+        i += 1;
+    }
+    table
+};
+
+// Flags a `STEP` entry for vowels that are inconsistent with the checksum.
+const INVALID_STEP: u32 = 1 << 31;
+
+// The number of rows in `STEP`. A row is a checksum state plus the shares of
+// three consonants, each less than 36.
+const STEP_ROWS: usize = 4 * 36;
+
+// For every checksum state `c` and pair of vowel indexes `left` and `right`
+// of a 3-tuple, at index `36 * c + 6 * left + right`, the part of the next
+// checksum state `36 * ((5 * c + 7 * bits) % 36)` that depends on `c` in the
+// low half and the data bits the vowels encode above it.
+//
+// The rows repeat every 36 states, so the row of the next word is the sum of
+// the low half and the share of the consonants without reducing it.
+//
+// Entries for vowels that are inconsistent with the checksum are
+// `INVALID_STEP`.
+static STEP: [u32; 36 * STEP_ROWS] = {
+    let mut table = [INVALID_STEP; 36 * STEP_ROWS];
+    let mut row = 0;
+    while row < STEP_ROWS {
+        let checksum = row % 36;
+        let mut left = 0;
+        while left < 6 {
+            let mut right = 0;
+            while right < 6 {
+                let high = HIGH_BITS[checksum][left];
+                let low = LOW_BITS[checksum][right];
+                if high != INVALID_BITS && low != INVALID_BITS {
+                    let bits = ((high << 6) | low) as usize;
+                    let next = (5 * checksum + 7 * bits) % 36;
+                    table[36 * row + 6 * left + right] = ((36 * next) | (bits << 16)) as u32;
+                }
+                right += 1;
+            }
+            left += 1;
+        }
+        row += 1;
+    }
     table
 };
 
+#[cfg(feature = "alloc")]
 pub fn inner(encoded: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = Vec::new();
+    decode_append(encoded, &mut decoded)?;
+    Ok(decoded)
+}
+
+/// Decode `encoded` and append the decoded bytes to `out`.
+///
+/// If decoding fails, `out` is left unchanged.
+#[cfg(feature = "alloc")]
+pub fn decode_append(encoded: &[u8], out: &mut Vec<u8>) -> Result<(), DecodeError> {
     // `xexax` is the encoded representation of an empty byte string. Test for
     // it directly to short circuit.
     if encoded == b"xexax" {
-        return Ok(Vec::new());
+        return Ok(());
+    }
+    let enc = strip_header_trailer(encoded)?;
+    let start = out.len();
+    out.resize(start + max_decoded_len(enc.len()), 0);
+    match decode_into(enc, &mut out[start..]) {
+        Ok(len) => {
+            out.truncate(start + len);
+            Ok(())
+        }
+        Err(err) => {
+            out.truncate(start);
+            Err(err)
+        }
     }
-    let enc = match encoded {
-        [HEADER, enc @ .., TRAILER] => enc,
-        [HEADER, ..] => return Err(DecodeError::MalformedTrailer),
-        [.., TRAILER] => return Err(DecodeError::MalformedHeader),
-        _ => return Err(DecodeError::Corrupted),
+}
+
+/// Return an upper bound for the number of bytes decoded from an encoding
+/// with `len` bytes between its header and trailer.
+#[inline]
+pub const fn max_decoded_len(len: usize) -> usize {
+    2 * (len / 6) + 1
+}
+
+/// The number of words decoded into a stack buffer at a time by
+/// [`decode_state`] and [`decode_blocks`].
+#[cfg(feature = "alloc")]
+pub const BLOCK_WORDS: usize = 256;
+
+/// Validate an encoding and return the checksum after its last full word and
+/// the final byte of odd length data.
+///
+/// This is the state needed to continue the encoding with more data. Only
+/// canonical encodings, those produced by [`encode`](crate::encode()), have
+/// such a state. Inputs with a word missing its separator are rejected even
+/// where the decoder accepts them.
+#[cfg(feature = "alloc")]
+pub fn decode_state(encoded: &[u8]) -> Result<(Checksum, Option<u8>), DecodeError> {
+    let enc = strip_header_trailer(encoded)?;
+    let (words, remainder) = enc.split_at(enc.len() - enc.len() % 6);
+    let mut out = [0; 2 * BLOCK_WORDS];
+    let mut checksum = Checksum::initial();
+    for block in words.chunks(6 * BLOCK_WORDS) {
+        match decode_words(block, checksum, &mut out) {
+            Ok((pairs, next)) if 6 * pairs == block.len() => checksum = next,
+            _ => return Err(state_error(encoded)),
+        }
+    }
+    let &[left, mid, right] = remainder else {
+        return Err(state_error(encoded));
     };
-    // This validation step ensures that the encoded byte string only contains
-    // ASCII bytes in the 24 character encoding alphabet.
-    //
-    // Code below must still handle None results from `find_byte` because bytes
-    // may not be from the right subset of the alphabet, e.g. a vowel present
-    // when a consonant is expected.
-    if let Some((_, pos)) = enc
-        .iter()
-        .zip(1_usize..) // start `pos` at 1 because we stripped off a leading 'x'
-        .find(|&(&byte, _)| ALPHABET_TABLE[usize::from(byte)] == 0)
-    {
+    match decode_tail([left, mid, right], checksum) {
+        Ok(byte) => Ok((checksum, byte)),
+        Err(_) => Err(state_error(encoded)),
+    }
+}
+
+/// Report the error the decoder would for an encoding rejected by
+/// [`decode_state`].
+#[cfg(feature = "alloc")]
+#[cold]
+fn state_error(encoded: &[u8]) -> DecodeError {
+    // Errors in blocks are reported relative to the block, and the decoder
+    // checks the whole input for invalid bytes first. Defer to it for
+    // consistent errors. Non-canonical inputs may still decode.
+    inner(encoded).err().unwrap_or(DecodeError::Corrupted)
+}
+
+/// Decode `encoded` block by block and pass the decoded bytes to `write` as
+/// they are decoded. Returns the number of decoded bytes.
+///
+/// The output and errors are identical to [`inner`]. Bytes decoded before an
+/// error may have been passed to `write`.
+#[cfg(feature = "alloc")]
+pub fn decode_blocks<F: FnMut(&[u8])>(encoded: &[u8], mut write: F) -> Result<usize, DecodeError> {
+    let enc = strip_header_trailer(encoded)?;
+    let (words, remainder) = enc.split_at(enc.len() - enc.len() % 6);
+    let mut decoded = [0; 2 * BLOCK_WORDS];
+    let mut checksum = Checksum::initial();
+    let mut written = 0;
+    for block in words.chunks(6 * BLOCK_WORDS) {
+        match decode_words(block, checksum, &mut decoded) {
+            Ok((pairs, next)) if 6 * pairs == block.len() => {
+                write(&decoded[..2 * pairs]);
+                written += 2 * pairs;
+                checksum = next;
+            }
+            _ => return decode_rest(encoded, written, write),
+        }
+    }
+    let &[left, mid, right] = remainder else {
+        return decode_rest(encoded, written, write);
+    };
+    match decode_tail([left, mid, right], checksum) {
+        Ok(Some(byte)) => {
+            write(&[byte]);
+            Ok(written + 1)
+        }
+        Ok(None) => Ok(written),
+        Err(_) => decode_rest(encoded, written, write),
+    }
+}
+
+/// Finish decoding an encoding rejected by [`decode_blocks`] after `written`
+/// bytes were passed to `write`.
+#[cfg(feature = "alloc")]
+#[cold]
+fn decode_rest<F: FnMut(&[u8])>(
+    encoded: &[u8],
+    written: usize,
+    mut write: F,
+) -> Result<usize, DecodeError> {
+    // Errors in blocks are reported relative to the block, and the decoder
+    // checks the whole input for invalid bytes first. Defer to it for
+    // consistent errors. Non-canonical inputs may still decode.
+    let decoded = inner(encoded)?;
+    write(&decoded[written..]);
+    Ok(decoded.len())
+}
+
+/// Decode the bytes between the header and trailer of an encoding into `out`
+/// and return the number of decoded bytes.
+///
+/// This is a single pass over the input with table lookups for the class of
+/// every byte and for the data bits carried by every vowel. Malformed input
+/// is rare, so once the fast path finds something unexpected it defers to
+/// `decode_error` to report the same error as a step-by-step validation would.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than [`max_decoded_len`].
+pub fn decode_into(enc: &[u8], out: &mut [u8]) -> Result<usize, DecodeError> {
+    let (len, last) = decode_parts(enc, out)?;
+    if let Some(byte) = last {
+        out[len] = byte;
+        return Ok(len + 1);
+    }
+    Ok(len)
+}
+
+/// Decode a Bubble Babble-encoded byte slice into the front of `out` and
+/// return the number of decoded bytes.
+pub fn decode_slice(encoded: &[u8], out: &mut [u8]) -> Result<usize, DecodeSliceError> {
+    let enc = strip_header_trailer(encoded)?;
+    if out.len() >= max_decoded_len(enc.len()) {
+        return Ok(decode_into(enc, out)?);
+    }
+    // `out` may still fit the data of an encoding of even length data, or of
+    // an encoding with a word missing its separator.
+    if out.len() < 2 * (enc.len() / 6) {
+        return Err(DecodeSliceError::OutputTooSmall);
+    }
+    let (len, last) = decode_parts(enc, out)?;
+    if let Some(byte) = last {
+        let slot = out.get_mut(len).ok_or(DecodeSliceError::OutputTooSmall)?;
+        *slot = byte;
+        return Ok(len + 1);
+    }
+    Ok(len)
+}
+
+/// Decode the bytes between the header and trailer of an encoding into `out`
+/// like [`decode_into`] and return the number of bytes decoded from full
+/// words and the final byte of odd length data.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than two bytes for every 6 byte chunk of `enc`.
+fn decode_parts(enc: &[u8], out: &mut [u8]) -> Result<(usize, Option<u8>), DecodeError> {
+    let (pairs, checksum) = decode_words(enc, Checksum::initial(), out)?;
+    let len = 2 * pairs;
+    let remainder = &enc[enc.len() - enc.len() % 6..];
+    // Chunks that are missing their separator end the run of full tuples.
+    // Decoding continues with the final partial tuple, which will most likely
+    // fail to checksum, after validating the skipped bytes.
+    let start = if pairs == enc.len() / 6 {
+        enc.len() - remainder.len()
+    } else {
+        0
+    };
+    if let Some(pos) = find_invalid_byte(enc, start) {
         return Err(DecodeError::InvalidByte(pos));
     }
-    let mut decoded = {
-        let len = encoded.len();
-        Vec::with_capacity(if len == 5 { 1 } else { 2 * ((len + 1) / 6) })
+    let &[left, mid, right] = remainder else {
+        return Err(DecodeError::Corrupted);
     };
-    let mut checksum = 1_u8;
-    let mut chunks = enc.chunks_exact(6);
-    while let Some(&[left, mid, right, up, b'-', down]) = chunks.next() {
-        let byte1 = decode_3_tuple(
-            index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?,
-            index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?,
-            index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?,
-            checksum,
-        )?;
-        let byte2 = decode_2_tuple(
-            index_from_consonant(up).ok_or(DecodeError::ExpectedConsonant)?,
-            index_from_consonant(down).ok_or(DecodeError::ExpectedConsonant)?,
-        );
-        checksum =
-            ((u16::from(checksum * 5) + (u16::from(byte1) * 7) + u16::from(byte2)) % 36) as u8;
-        decoded.push(byte1);
-        decoded.push(byte2);
-    }
-    if let [left, mid, right] = *chunks.remainder() {
-        let a = index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?;
-        let c = index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?;
-
-        match mid {
-            b'x' if a != checksum % 6 || c != checksum / 6 => Err(DecodeError::ChecksumMismatch),
-            b'x' => Ok(decoded),
-            _ => {
-                let b = index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?;
-                let byte = decode_3_tuple(a, b, c, checksum)?;
-                decoded.push(byte);
-                Ok(decoded)
-            }
+    let last = decode_tail([left, mid, right], checksum)?;
+    Ok((len, last))
+}
+
+/// Decode the run of 6 byte words at the start of `enc` into `out`, starting
+/// from the given checksum state.
+///
+/// Decoding stops at the end of the last full word or at the first word that
+/// is missing its `-` separator. Returns the number of decoded pairs and the
+/// checksum after the last of them.
+///
+/// Positions in [`DecodeError::InvalidByte`] errors are offset by one for a
+/// stripped header.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than two bytes for every word.
+#[inline]
+pub fn decode_words(
+    enc: &[u8],
+    checksum: Checksum,
+    out: &mut [u8],
+) -> Result<(usize, Checksum), DecodeError> {
+    let out = &mut out[..2 * (enc.len() / 6)];
+    // The checksum state scaled by 36, the start of its row in `STEP`.
+    let mut row = 36 * usize::from(checksum.value());
+    let mut len = 0;
+    for (chunk, pair) in enc.chunks_exact(6).zip(out.chunks_exact_mut(2)) {
+        let &[left, mid, right, up, sep, down] = chunk else {
+            unreachable!("chunks are 6 bytes");
+        };
+        if sep != b'-' {
+            break;
+        }
+        let word = WORD_TABLE[0][usize::from(left)]
+            + WORD_TABLE[1][usize::from(mid)]
+            + WORD_TABLE[2][usize::from(right)]
+            + WORD_TABLE[3][usize::from(up)]
+            + WORD_TABLE[4][usize::from(down)];
+        if word & INVALID_WORD != 0 {
+            let letters = [left, mid, right, up, down];
+            return Err(decode_error(enc, letters, row_checksum(row)));
+        }
+        let entry = STEP[row + (word & VOWELS_MASK) as usize];
+        if entry & INVALID_STEP != 0 {
+            let letters = [left, mid, right, up, down];
+            return Err(decode_error(enc, letters, row_checksum(row)));
         }
+        let bits = (word >> PAIR_SHIFT) as u16 | (entry >> 16) as u16;
+        row = usize::from(entry as u16) + ((word >> SHARE_SHIFT) & SHARE_MASK) as usize;
+        pair.copy_from_slice(&bits.to_le_bytes());
+        len += 1;
+    }
+    Ok((len, row_checksum(row)))
+}
+
+/// Return the checksum state of a row of `STEP`.
+#[inline]
+fn row_checksum(row: usize) -> Checksum {
+    let value = u8::try_from(row / 36 % 36).expect("checksum states are less than 36");
+    Checksum::from_value(value).expect("checksum states are less than 36")
+}
+
+/// Decode the final 3-tuple of an encoding.
+///
+/// Returns the last byte of odd length data, or `None` if the tuple is a
+/// checksum word.
+#[inline]
+pub fn decode_tail(tail: [u8; 3], checksum: Checksum) -> Result<Option<u8>, DecodeError> {
+    let [left, mid, right] = tail;
+    let checksum = checksum.value();
+    let a = index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?;
+    let c = index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?;
+    if mid == b'x' {
+        if a != checksum % 6 || c != checksum / 6 {
+            return Err(DecodeError::ChecksumMismatch);
+        }
+        Ok(None)
     } else {
-        Err(DecodeError::Corrupted)
+        let b = index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?;
+        decode_3_tuple(a, b, c, checksum).map(Some)
+    }
+}
+
+/// Decode the checksum-independent bits of a run of 6 byte chunks.
+///
+/// For every chunk, the middle consonant of the 3-tuple and the consonant
+/// pair are written to the two bytes of `out` and the indexes of the vowels of
+/// the 3-tuple to `vowels`. The remaining bits are filled in by
+/// [`decode_vowels`].
+///
+/// Returns `false` if any chunk is malformed. The caller must then decode
+/// the input with [`decode_into`] to determine the error.
+#[cfg(feature = "rayon")]
+pub fn decode_consonants(enc: &[u8], out: &mut [u8], vowels: &mut [[u8; 2]]) -> bool {
+    debug_assert_eq!(enc.len(), 6 * vowels.len());
+    debug_assert_eq!(out.len(), 2 * vowels.len());
+
+    let chunks = enc.chunks_exact(6).zip(out.chunks_exact_mut(2)).zip(vowels);
+    for ((chunk, pair), vowels) in chunks {
+        let &[left, mid, right, up, sep, down] = chunk else {
+            return false;
+        };
+        let left = DECODE_TABLE[usize::from(left)];
+        let mid = DECODE_TABLE[usize::from(mid)];
+        let right = DECODE_TABLE[usize::from(right)];
+        let up = DECODE_TABLE[usize::from(up)];
+        let down = DECODE_TABLE[usize::from(down)];
+        if sep != b'-' || (left & right & VOWEL) == 0 || (mid & up & down & CONSONANT) == 0 {
+            return false;
+        }
+        pair[0] = (mid & INDEX_MASK) << 2;
+        pair[1] = ((up & INDEX_MASK) << 4) | (down & INDEX_MASK);
+        *vowels = [left & INDEX_MASK, right & INDEX_MASK];
+    }
+    true
+}
+
+/// Fill in the checksum-dependent bits of the pairs decoded by
+/// [`decode_consonants`], starting from the given checksum state.
+///
+/// Returns the checksum after the last pair, or `None` if a vowel is
+/// inconsistent with the checksum.
+#[cfg(feature = "rayon")]
+pub fn decode_vowels(
+    out: &mut [u8],
+    vowels: &[[u8; 2]],
+    mut checksum: Checksum,
+) -> Option<Checksum> {
+    debug_assert_eq!(out.len(), 2 * vowels.len());
+
+    for (pair, &[left, right]) in out.chunks_exact_mut(2).zip(vowels) {
+        let checksum_row = usize::from(checksum.value());
+        let high = HIGH_BITS[checksum_row][usize::from(left)];
+        let low = LOW_BITS[checksum_row][usize::from(right)];
+        if (high | low) == INVALID_BITS {
+            return None;
+        }
+        pair[0] |= (high << 6) | low;
+        checksum = checksum.update([pair[0], pair[1]]);
+    }
+    Some(checksum)
+}
+
+/// Determine the error for a 6 byte chunk rejected by the fast path of
+/// `decode_into`.
+///
+/// Errors are reported with the same precedence as a step-by-step validation:
+/// bytes outside the alphabet anywhere in the input first, then the bytes of
+/// the chunk in order.
+#[cold]
+fn decode_error(enc: &[u8], letters: [u8; 5], checksum: Checksum) -> DecodeError {
+    if let Some(pos) = find_invalid_byte(enc, 0) {
+        return DecodeError::InvalidByte(pos);
     }
+    let [left, mid, right, up, down] = letters.map(|letter| DECODE_TABLE[usize::from(letter)]);
+    if left & VOWEL == 0 {
+        return DecodeError::ExpectedVowel;
+    }
+    if mid & CONSONANT == 0 {
+        return DecodeError::ExpectedConsonant;
+    }
+    if right & VOWEL == 0 {
+        return DecodeError::ExpectedVowel;
+    }
+    let checksum_row = usize::from(checksum.value());
+    if HIGH_BITS[checksum_row][usize::from(left & INDEX_MASK)] == INVALID_BITS
+        || LOW_BITS[checksum_row][usize::from(right & INDEX_MASK)] == INVALID_BITS
+    {
+        return DecodeError::Corrupted;
+    }
+    debug_assert!((up & down & CONSONANT) == 0);
+    DecodeError::ExpectedConsonant
 }
 
+/// Return the position of the first byte at or after `start` that is not in
+/// the encoding alphabet, accounting for the stripped leading `x`.
 #[inline]
-fn index_from_consonant(consonant: u8) -> Option<u8> {
-    let index = match consonant {
-        b'b' => 0,
-        b'c' => 1,
-        b'd' => 2,
-        b'f' => 3,
-        b'g' => 4,
-        b'h' => 5,
-        b'k' => 6,
-        b'l' => 7,
-        b'm' => 8,
-        b'n' => 9,
-        b'p' => 10,
-        b'r' => 11,
-        b's' => 12,
-        b't' => 13,
-        b'v' => 14,
-        b'z' => 15,
-        _ => return None,
-    };
-    Some(index)
+pub fn find_invalid_byte(enc: &[u8], start: usize) -> Option<usize> {
+    enc.get(start..)?
+        .iter()
+        .position(|&byte| !is_alphabet(byte))
+        // add 1 because we stripped off a leading 'x'
+        .map(|pos| start + pos + 1)
 }
 
+/// Strip the leading `x` header and trailing `x` trailer from the given
+/// encoded byte string.
 #[inline]
-fn index_from_vowel(vowel: u8) -> Option<u8> {
-    let index = match vowel {
-        b'a' => 0,
-        b'e' => 1,
-        b'i' => 2,
-        b'o' => 3,
-        b'u' => 4,
-        b'y' => 5,
-        _ => return None,
-    };
-    Some(index)
+pub fn strip_header_trailer(encoded: &[u8]) -> Result<&[u8], DecodeError> {
+    match encoded {
+        [HEADER, enc @ .., TRAILER] => Ok(enc),
+        [HEADER, ..] => Err(DecodeError::MalformedTrailer),
+        [.., TRAILER] => Err(DecodeError::MalformedHeader),
+        _ => Err(DecodeError::Corrupted),
+    }
 }
 
+/// Strip the leading `x` header and trailing `x` trailer from the given
+/// encoded byte string and validate that the bytes between them are in the
+/// encoding alphabet.
+///
+/// Callers must still handle bytes that are from the wrong subset of the
+/// alphabet, e.g. a vowel present when a consonant is expected.
+pub fn strip_frame(encoded: &[u8]) -> Result<&[u8], DecodeError> {
+    let enc = strip_header_trailer(encoded)?;
+    // This validation step ensures that the encoded byte string only contains
+    // ASCII bytes in the 24 character encoding alphabet.
+    if let Some(pos) = find_invalid_byte(enc, 0) {
+        return Err(DecodeError::InvalidByte(pos));
+    }
+    Ok(enc)
+}
+
+/// Return whether the given byte is in the 24 character encoding alphabet.
 #[inline]
-fn decode_3_tuple(byte1: u8, byte2: u8, byte3: u8, checksum: u8) -> Result<u8, DecodeError> {
-    // Will not overflow since:
-    // - `byte1` is guaranteed to be ASCII or < 128.
-    // Will not underflow since:
-    // - 6 - (checksum % 6) > 0
-    let high = (byte1 + 6 - (checksum % 6)) % 6;
+pub fn is_alphabet(byte: u8) -> bool {
+    DECODE_TABLE[usize::from(byte)] != 0
+}
+
+#[inline]
+pub fn index_from_consonant(consonant: u8) -> Option<u8> {
+    let entry = DECODE_TABLE[usize::from(consonant)];
+    if entry & CONSONANT == 0 {
+        None
+    } else {
+        Some(entry & INDEX_MASK)
+    }
+}
+
+#[inline]
+pub fn index_from_vowel(vowel: u8) -> Option<u8> {
+    let entry = DECODE_TABLE[usize::from(vowel)];
+    if entry & VOWEL == 0 {
+        None
+    } else {
+        Some(entry & INDEX_MASK)
+    }
+}
+
+#[inline]
+pub fn decode_3_tuple(byte1: u8, byte2: u8, byte3: u8, checksum: u8) -> Result<u8, DecodeError> {
+    // Panic safety:
+    //
+    // - `checksum` is constructed with mod 36.
+    // - `byte1` and `byte3` are vowel indexes, which are less than 6.
+    let high = HIGH_BITS[usize::from(checksum)][usize::from(byte1)];
     let mid = byte2;
-    // Will not overflow since:
-    // - `byte3` is guaranteed to be ASCII or < 128.
-    // Will not underflow since:
-    // - 6 - ((checksum / 6) % 6) > 0
-    let low = (byte3 + 6 - ((checksum / 6) % 6)) % 6;
-    if high >= 4 || low >= 4 {
+    let low = LOW_BITS[usize::from(checksum)][usize::from(byte3)];
+    if high == INVALID_BITS || low == INVALID_BITS {
         Err(DecodeError::Corrupted)
     } else {
         Ok((high << 6) | (mid << 2) | low)
@@ -147,7 +628,7 @@ fn decode_3_tuple(byte1: u8, byte2: u8, byte3: u8, checksum: u8) -> Result<u8, D
 }
 
 #[inline]
-fn decode_2_tuple(byte1: u8, byte2: u8) -> u8 {
+pub fn decode_2_tuple(byte1: u8, byte2: u8) -> u8 {
     (byte1 << 4) | byte2
 }
 
@@ -305,4 +786,34 @@ mod tests {
         //   low = (4 + 6 - 0) % 6 = 10 % 6 = 4 (invalid since it must be < 4).
         assert_eq!(decode_3_tuple(0, 0, 4, 0), Err(DecodeError::Corrupted));
     }
+
+    #[test]
+    fn test_decode_slice_output_sizes() {
+        let cases: [(&[u8], &[u8]); 3] = [
+            (b"xexax", b""),
+            (b"xesef-disof-gytuf-katof-movif-baxux", b"1234567890"),
+            (b"xigak-nyryk-humil-bosek-sonax", b"Pineapple"),
+        ];
+        for (encoded, expected) in cases {
+            for len in 0..=expected.len() + 2 {
+                let mut out = [0; 16];
+                let result = decode_slice(encoded, &mut out[..len]);
+                if len < expected.len() {
+                    assert_eq!(result, Err(DecodeSliceError::OutputTooSmall));
+                } else {
+                    assert_eq!(result, Ok(expected.len()));
+                    assert_eq!(&out[..expected.len()], expected);
+                }
+            }
+        }
+        let mut out = [0; 16];
+        assert_eq!(
+            decode_slice(b"xigak-nyryk-humil-bosek-sonox", &mut out),
+            Err(DecodeSliceError::Decode(DecodeError::Corrupted))
+        );
+        assert_eq!(
+            decode_slice(b"yx", &mut out),
+            Err(DecodeSliceError::Decode(DecodeError::MalformedHeader))
+        );
+    }
 }
@@ -0,0 +1,313 @@
+use alloc::vec::Vec;
+use core::cmp;
+use core::ops::Range;
+
+use crate::words::{Token, Word, Words};
+use crate::DecodeError;
+
+/// How a word differs between two Bubble Babble encodings.
+///
+/// See [`WordDiff`] and [`boba::diff`](crate::diff()).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WordStatus {
+    /// The word is identical in both encodings.
+    Unchanged,
+    /// The bytes carried by the word differ between the encodings.
+    DataChanged,
+    /// The word carries the same bytes in both encodings, but its vowels
+    /// differ because the running checksum was altered by an earlier change.
+    ChecksumChanged,
+    /// The word is only present in the left encoding.
+    Removed,
+    /// The word is only present in the right encoding.
+    Added,
+}
+
+/// The comparison of a single word position in a [`Diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WordDiff {
+    index: usize,
+    status: WordStatus,
+    left: Option<Range<usize>>,
+    right: Option<Range<usize>>,
+    bytes: Range<usize>,
+}
+
+impl WordDiff {
+    /// Return the zero-based position of this word in the encodings.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Return how this word differs between the two encodings.
+    #[must_use]
+    pub fn status(&self) -> WordStatus {
+        self.status
+    }
+
+    /// Return the byte span of this word in the left encoding, if present.
+    #[must_use]
+    pub fn left(&self) -> Option<Range<usize>> {
+        self.left.clone()
+    }
+
+    /// Return the byte span of this word in the right encoding, if present.
+    #[must_use]
+    pub fn right(&self) -> Option<Range<usize>> {
+        self.right.clone()
+    }
+
+    /// Return the range of decoded bytes this word carries data for in either
+    /// encoding.
+    ///
+    /// See [`Word::bytes`] for details on how words map to decoded bytes.
+    #[must_use]
+    pub fn bytes(&self) -> Range<usize> {
+        self.bytes.clone()
+    }
+}
+
+/// A word-aligned comparison of two Bubble Babble encodings.
+///
+/// This struct is created by [`boba::diff`](crate::diff()) and
+/// [`boba::diff_data`](crate::diff_data()). See their documentation for more.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diff {
+    words: Vec<WordDiff>,
+    changed: Vec<Range<usize>>,
+}
+
+impl Diff {
+    /// Return the per-word comparison of the two encodings.
+    #[must_use]
+    pub fn words(&self) -> &[WordDiff] {
+        &self.words
+    }
+
+    /// Return whether the two encodings are identical.
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.changed.is_empty()
+    }
+
+    /// Return the first word whose data differs between the two encodings.
+    ///
+    /// Words after this one may differ only in their checksum vowels.
+    #[must_use]
+    pub fn first_data_change(&self) -> Option<&WordDiff> {
+        self.words.iter().find(|word| {
+            matches!(
+                word.status,
+                WordStatus::DataChanged | WordStatus::Removed | WordStatus::Added
+            )
+        })
+    }
+
+    /// Return the ranges of decoded bytes that differ between the two
+    /// encodings.
+    ///
+    /// Ranges are sorted and non-overlapping. If one input is longer than the
+    /// other, its extra bytes are reported as changed.
+    #[must_use]
+    pub fn changed_bytes(&self) -> &[Range<usize>] {
+        &self.changed
+    }
+}
+
+pub fn inner(left: &[u8], right: &[u8]) -> Result<Diff, DecodeError> {
+    let left_words = collect_words(left)?;
+    let right_words = collect_words(right)?;
+    let left_data = crate::decode::inner(left)?;
+    let right_data = crate::decode::inner(right)?;
+
+    let len = cmp::max(left_words.len(), right_words.len());
+    let mut words = Vec::with_capacity(len);
+    for index in 0..len {
+        let (status, left, right, bytes) = match (left_words.get(index), right_words.get(index)) {
+            (Some(l), Some(r)) => {
+                let status = if left[l.span()] == right[r.span()] {
+                    WordStatus::Unchanged
+                } else if word_data(l, &left_data) == word_data(r, &right_data) {
+                    WordStatus::ChecksumChanged
+                } else {
+                    WordStatus::DataChanged
+                };
+                let bytes = cmp::min(l.bytes().start, r.bytes().start)
+                    ..cmp::max(l.bytes().end, r.bytes().end);
+                (status, Some(l.span()), Some(r.span()), bytes)
+            }
+            (Some(l), None) => (WordStatus::Removed, Some(l.span()), None, l.bytes()),
+            (None, Some(r)) => (WordStatus::Added, None, Some(r.span()), r.bytes()),
+            (None, None) => unreachable!("index is less than the length of one of the word lists"),
+        };
+        words.push(WordDiff {
+            index,
+            status,
+            left,
+            right,
+            bytes,
+        });
+    }
+
+    Ok(Diff {
+        words,
+        changed: changed_ranges(&left_data, &right_data),
+    })
+}
+
+fn collect_words(encoded: &[u8]) -> Result<Vec<Word>, DecodeError> {
+    let mut words = Vec::new();
+    for token in Words::new(encoded) {
+        if let Token::Word(word) = token? {
+            words.push(word);
+        }
+    }
+    Ok(words)
+}
+
+/// Extract the data carried by a word, ignoring the checksum.
+///
+/// A word carries the low four bits of the byte before its vowel-consonant-
+/// vowel triple, the full byte encoded by the triple, and the high four bits
+/// of the byte after it.
+fn word_data(word: &Word, data: &[u8]) -> [Option<u8>; 3] {
+    let index = word.index();
+    let low = if index > 0 {
+        data.get(2 * index - 1).map(|byte| byte & 0x0F)
+    } else {
+        None
+    };
+    let byte = if word.is_checksum() {
+        None
+    } else {
+        data.get(2 * index).copied()
+    };
+    let high = if word.is_final() {
+        None
+    } else {
+        data.get(2 * index + 1).map(|byte| byte >> 4)
+    };
+    [low, byte, high]
+}
+
+fn changed_ranges(left: &[u8], right: &[u8]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let len = cmp::max(left.len(), right.len());
+    for pos in 0..len {
+        if left.get(pos) == right.get(pos) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.end == pos => last.end = pos + 1,
+            _ => ranges.push(pos..pos + 1),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn statuses(diff: &Diff) -> Vec<WordStatus> {
+        diff.words().iter().map(WordDiff::status).collect()
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let diff = inner(
+            b"xigak-nyryk-humil-bosek-sonax",
+            b"xigak-nyryk-humil-bosek-sonax",
+        )
+        .unwrap();
+        assert!(diff.is_identical());
+        assert_eq!(diff.first_data_change(), None);
+        assert!(diff.changed_bytes().is_empty());
+        assert_eq!(statuses(&diff), [WordStatus::Unchanged; 5]);
+    }
+
+    #[test]
+    fn test_diff_checksum_propagation() {
+        // "Pineapple" vs "Pinfapple"
+        let diff = inner(
+            b"xigak-nyryk-humil-bosek-sonax",
+            b"xigak-nyryk-kymil-bisik-sunux",
+        )
+        .unwrap();
+        assert_eq!(
+            statuses(&diff),
+            [
+                WordStatus::Unchanged,
+                WordStatus::Unchanged,
+                WordStatus::DataChanged,
+                WordStatus::ChecksumChanged,
+                WordStatus::ChecksumChanged,
+            ]
+        );
+        assert_eq!(diff.changed_bytes(), [3..4]);
+        let first = diff.first_data_change().unwrap();
+        assert_eq!(first.index(), 2);
+        assert_eq!(first.bytes(), 3..6);
+        assert_eq!(first.left(), Some(12..17));
+        assert_eq!(first.right(), Some(12..17));
+    }
+
+    #[test]
+    fn test_diff_different_lengths() {
+        // "Pineapple" vs "Pineapples"
+        let diff = inner(
+            b"xigak-nyryk-humil-bosek-sonax",
+            b"xigak-nyryk-humil-bosek-sonal-fuxex",
+        )
+        .unwrap();
+        assert_eq!(
+            statuses(&diff),
+            [
+                WordStatus::Unchanged,
+                WordStatus::Unchanged,
+                WordStatus::Unchanged,
+                WordStatus::Unchanged,
+                WordStatus::DataChanged,
+                WordStatus::Added,
+            ]
+        );
+        assert_eq!(diff.changed_bytes(), [9..10]);
+        assert_eq!(diff.words()[5].left(), None);
+        assert_eq!(diff.words()[5].right(), Some(30..34));
+        assert_eq!(diff.words()[5].bytes(), 9..10);
+    }
+
+    #[test]
+    fn test_diff_last_byte_only() {
+        // "Pineapple" vs "Pineapplf"
+        let diff = inner(
+            b"xigak-nyryk-humil-bosek-sonax",
+            b"xigak-nyryk-humil-bosek-sonex",
+        )
+        .unwrap();
+        assert_eq!(diff.first_data_change().map(WordDiff::index), Some(4));
+        assert_eq!(diff.changed_bytes(), [8..9]);
+    }
+
+    #[test]
+    fn test_changed_ranges() {
+        assert_eq!(changed_ranges(b"", b""), vec![]);
+        assert_eq!(changed_ranges(b"abcd", b"abcd"), vec![]);
+        assert_eq!(changed_ranges(b"abcd", b"xbyy"), vec![0..1, 2..4]);
+        assert_eq!(changed_ranges(b"ab", b"abcd"), vec![2..4]);
+    }
+
+    #[test]
+    fn test_diff_invalid_input() {
+        assert_eq!(
+            inner(b"xexax", b"xesef-disof-gytuf-katof-movif-baxax"),
+            Err(DecodeError::ChecksumMismatch)
+        );
+        assert_eq!(inner(b"yx", b"xexax"), Err(DecodeError::MalformedHeader));
+    }
+}
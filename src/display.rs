@@ -0,0 +1,121 @@
+#[cfg(feature = "defmt")]
+use core::convert::Infallible;
+use core::fmt;
+
+use crate::encode::{ascii_to_str, tail, write_pairs};
+use crate::Checksum;
+
+/// The number of byte pairs encoded into the stack buffer at a time.
+const CHUNK_PAIRS: usize = 64;
+
+/// A wrapper that formats a byte slice with the Bubble Babble encoding.
+///
+/// The [`Display`](fmt::Display) implementation encodes the data in small
+/// chunks on the stack, so encodings can be written to any
+/// [`fmt::Write`](core::fmt::Write) sink without allocating. Width, fill and
+/// alignment flags are ignored.
+///
+/// With the **defmt** feature, the wrapper also implements `defmt::Format`, so
+/// firmware can log the same encodings with deferred formatting.
+///
+/// This struct is created by [`boba::display`](crate::display()).
+///
+/// # Examples
+///
+/// ```
+/// use boba::BubbleBabbleDisplay;
+///
+/// let display = BubbleBabbleDisplay::new(b"1234567890");
+/// assert_eq!(display.to_string(), "xesef-disof-gytuf-katof-movif-baxux");
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BubbleBabbleDisplay<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BubbleBabbleDisplay<'a> {
+    /// Create a wrapper that formats `data` with the Bubble Babble encoding.
+    #[must_use]
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Encode the data in chunks on the stack and pass the pieces of the
+    /// encoding to `write` in order.
+    fn write_pieces<E>(&self, mut write: impl FnMut(&str) -> Result<(), E>) -> Result<(), E> {
+        let (pairs, remainder) = self.data.split_at(self.data.len() & !1);
+        let mut buf = [0; 6 * CHUNK_PAIRS];
+        let mut checksum = Checksum::initial();
+
+        write("x")?;
+        for chunk in pairs.chunks(2 * CHUNK_PAIRS) {
+            let words = &mut buf[..3 * chunk.len()];
+            checksum = write_pairs(chunk, checksum, words);
+            write(ascii_to_str(words))?;
+        }
+        write(ascii_to_str(&tail(remainder.first().copied(), checksum)))?;
+        write("x")
+    }
+}
+
+impl fmt::Display for BubbleBabbleDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_pieces(|piece| f.write_str(piece))
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for BubbleBabbleDisplay<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let Ok(()) = self.write_pieces(|piece| {
+            defmt::write!(f, "{=str}", piece);
+            Ok::<(), Infallible>(())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::str;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let cases: [(&[u8], &str); 4] = [
+            (b"", "xexax"),
+            (b"1234567890", "xesef-disof-gytuf-katof-movif-baxux"),
+            (b"Pineapple", "xigak-nyryk-humil-bosek-sonax"),
+            (b"xyz!x6", "xival-neved-cavuf-kexyx"),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(BubbleBabbleDisplay::new(data).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_display_matches_encode_slice() {
+        let data = (0..=255)
+            .cycle()
+            .take(6 * CHUNK_PAIRS + 3)
+            .collect::<Vec<u8>>();
+        let mut out = vec![0; 6 * data.len()];
+        for len in [
+            0,
+            1,
+            2 * CHUNK_PAIRS - 1,
+            2 * CHUNK_PAIRS,
+            2 * CHUNK_PAIRS + 1,
+            data.len(),
+        ] {
+            let data = &data[..len];
+            let encoded_len = crate::encode_slice(data, &mut out).unwrap();
+            let encoded = str::from_utf8(&out[..encoded_len]).unwrap();
+            assert_eq!(BubbleBabbleDisplay::new(data).to_string(), encoded);
+        }
+    }
+}
@@ -0,0 +1,614 @@
+use core::fmt;
+
+use embedded_io::{ErrorKind, ErrorType, Read, Write};
+
+use crate::encode::{tail, write_pairs};
+use crate::partial::Machine;
+use crate::{Checksum, DecodeError};
+
+/// The number of words encoded into the stack buffer at a time.
+///
+/// This is kept small for the stacks of embedded targets.
+const CHUNK_WORDS: usize = 16;
+
+/// A writer that encodes the data written to it with the Bubble Babble
+/// encoding and writes the encoding to an inner [`embedded_io::Write`]
+/// writer.
+///
+/// The running checksum and the last byte of odd length data are carried
+/// between writes, so data can be streamed without buffering it. The header
+/// is written before the first word, and the final 3-tuple and trailer are
+/// written by [`finish`](Self::finish). An encoding is incomplete until
+/// `finish` is called. Dropping the writer does not finish it.
+///
+/// With the **embedded-io-async** feature, the writer also implements
+/// [`embedded_io_async::Write`] for async inner writers, which are finished
+/// with [`finish_async`].
+///
+/// # Examples
+///
+/// ```
+/// use boba::EmbeddedEncodeWriter;
+/// use embedded_io::Write;
+///
+/// # fn example() -> Result<(), embedded_io::SliceWriteError> {
+/// let mut buf = [0; 64];
+/// let mut writer = EmbeddedEncodeWriter::new(&mut buf[..]);
+/// writer.write_all(b"Pine")?;
+/// writer.write_all(b"apple")?;
+/// let unused = writer.finish()?.len();
+/// assert_eq!(&buf[..64 - unused], b"xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// [`embedded_io_async::Write`]: https://docs.rs/embedded-io-async/latest/embedded_io_async/trait.Write.html
+#[cfg_attr(
+    feature = "embedded-io-async",
+    doc = "[`finish_async`]: Self::finish_async"
+)]
+#[cfg_attr(
+    not(feature = "embedded-io-async"),
+    doc = "[`finish_async`]: https://docs.rs/boba/latest/boba/struct.EmbeddedEncodeWriter.html#method.finish_async"
+)]
+#[derive(Debug)]
+pub struct EmbeddedEncodeWriter<W> {
+    inner: W,
+    checksum: Checksum,
+    // The last byte of odd length data written so far, which is not yet part
+    // of a word.
+    pending: Option<u8>,
+    started: bool,
+}
+
+impl<W> EmbeddedEncodeWriter<W> {
+    /// Create a writer that writes the encoding of its input to `inner`.
+    pub const fn new(inner: W) -> Self {
+        Self {
+            inner,
+            checksum: Checksum::initial(),
+            pending: None,
+            started: false,
+        }
+    }
+
+    /// Return a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Encode a prefix of the non-empty `data` into `out`.
+    ///
+    /// Returns the number of bytes of `data` consumed and the number of bytes
+    /// of `out` written.
+    fn encode_chunk(&mut self, data: &[u8], out: &mut [u8; 1 + 6 * CHUNK_WORDS]) -> (usize, usize) {
+        let mut written = 0;
+        if !self.started {
+            out[0] = b'x';
+            written = 1;
+            self.started = true;
+        }
+        let mut consumed = 0;
+        if let Some(left) = self.pending.take() {
+            let word = &mut out[written..written + 6];
+            self.checksum = write_pairs(&[left, data[0]], self.checksum, word);
+            written += 6;
+            consumed = 1;
+        }
+        let rest = &data[consumed..];
+        let words = (rest.len() / 2).min((out.len() - written) / 6);
+        let words_out = &mut out[written..written + 6 * words];
+        self.checksum = write_pairs(&rest[..2 * words], self.checksum, words_out);
+        written += 6 * words;
+        consumed += 2 * words;
+        if let [byte] = data[consumed..] {
+            self.pending = Some(byte);
+            consumed += 1;
+        }
+        (consumed, written)
+    }
+
+    /// Encode the end of the encoding into `out` and return its length.
+    fn encode_end(&mut self, out: &mut [u8; 5]) -> usize {
+        let mut written = 0;
+        if !self.started {
+            out[0] = b'x';
+            written = 1;
+            self.started = true;
+        }
+        out[written..written + 3].copy_from_slice(&tail(self.pending, self.checksum));
+        out[written + 3] = b'x';
+        written + 4
+    }
+}
+
+impl<W: Write> EmbeddedEncodeWriter<W> {
+    /// Write the end of the encoding and return the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the inner writer fails, the error is returned.
+    pub fn finish(mut self) -> Result<W, W::Error> {
+        let mut end = [0; 5];
+        let len = self.encode_end(&mut end);
+        self.inner.write_all(&end[..len])?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<W: embedded_io_async::Write> EmbeddedEncodeWriter<W> {
+    /// Write the end of the encoding to an async inner writer and return the
+    /// inner writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the inner writer fails, the error is returned.
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-io-async")))]
+    pub async fn finish_async(mut self) -> Result<W, W::Error> {
+        let mut end = [0; 5];
+        let len = self.encode_end(&mut end);
+        self.inner.write_all(&end[..len]).await?;
+        self.inner.flush().await?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: ErrorType> ErrorType for EmbeddedEncodeWriter<W> {
+    type Error = W::Error;
+}
+
+impl<W: Write> Write for EmbeddedEncodeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut out = [0; 1 + 6 * CHUNK_WORDS];
+        let (consumed, written) = self.encode_chunk(buf, &mut out);
+        self.inner.write_all(&out[..written])?;
+        Ok(consumed)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<W: embedded_io_async::Write> embedded_io_async::Write for EmbeddedEncodeWriter<W> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut out = [0; 1 + 6 * CHUNK_WORDS];
+        let (consumed, written) = self.encode_chunk(buf, &mut out);
+        self.inner.write_all(&out[..written]).await?;
+        Ok(consumed)
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush().await
+    }
+}
+
+/// An error returned by [`EmbeddedDecodeReader`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddedDecodeError<E> {
+    /// Reading from the inner reader failed.
+    Io(E),
+    /// The encoding read from the inner reader is invalid.
+    Decode(DecodeError),
+}
+
+impl<E> From<DecodeError> for EmbeddedDecodeError<E> {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for EmbeddedDecodeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Read failed: {err}"),
+            Self::Decode(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for EmbeddedDecodeError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Decode(err) => Some(err),
+        }
+    }
+}
+
+impl<E: embedded_io::Error + 'static> embedded_io::Error for EmbeddedDecodeError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Io(err) => err.kind(),
+            Self::Decode(_) => ErrorKind::InvalidData,
+        }
+    }
+}
+
+/// A reader that decodes a Bubble Babble encoding read from an inner
+/// [`embedded_io::Read`] reader.
+///
+/// The encoding is decoded one byte at a time as it is read, without
+/// buffering, so every read returns at most as many decoded bytes as it read
+/// encoded bytes. Invalid input is reported with
+/// [`EmbeddedDecodeError::Decode`] once the reader reaches it. Data before
+/// the invalid input has already been returned by then and must not be
+/// trusted until the reader returns end of file, when the checksum has been
+/// verified. Input after the trailer is invalid.
+///
+/// With the **embedded-io-async** feature, the reader also implements
+/// [`embedded_io_async::Read`] for async inner readers.
+///
+/// # Examples
+///
+/// ```
+/// use boba::EmbeddedDecodeReader;
+/// use embedded_io::Read;
+///
+/// # fn example() -> Result<(), boba::EmbeddedDecodeError<core::convert::Infallible>> {
+/// let mut reader = EmbeddedDecodeReader::new(&b"xigak-nyryk-humil-bosek-sonax"[..]);
+/// let mut buf = [0; 16];
+/// let mut len = 0;
+/// loop {
+///     match reader.read(&mut buf[len..])? {
+///         0 => break,
+///         read => len += read,
+///     }
+/// }
+/// assert_eq!(&buf[..len], b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// [`embedded_io_async::Read`]: https://docs.rs/embedded-io-async/latest/embedded_io_async/trait.Read.html
+#[derive(Debug)]
+pub struct EmbeddedDecodeReader<R> {
+    inner: R,
+    machine: Machine,
+    failed: Option<DecodeError>,
+}
+
+impl<R> EmbeddedDecodeReader<R> {
+    /// Create a reader that decodes the encoding read from `inner`.
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            machine: Machine::new(),
+            failed: None,
+        }
+    }
+
+    /// Return a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consume the reader and return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Decode `encoded` into the front of `out`, which is at least as long,
+    /// and return the number of decoded bytes.
+    ///
+    /// An empty `encoded` marks the end of the input.
+    fn decode(&mut self, encoded: &[u8], out: &mut [u8]) -> Result<usize, DecodeError> {
+        if let Some(err) = self.failed {
+            return Err(err);
+        }
+        if encoded.is_empty() && !self.machine.is_complete() {
+            self.failed = Some(DecodeError::MalformedTrailer);
+            return Err(DecodeError::MalformedTrailer);
+        }
+        let mut len = 0;
+        for &byte in encoded {
+            match self.machine.push(byte) {
+                Ok(Some(decoded)) => {
+                    out[len] = decoded;
+                    len += 1;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    // Return the bytes decoded before the error first.
+                    self.failed = Some(err);
+                    return if len > 0 { Ok(len) } else { Err(err) };
+                }
+            }
+        }
+        Ok(len)
+    }
+}
+
+impl<R: ErrorType> ErrorType for EmbeddedDecodeReader<R>
+where
+    R::Error: 'static,
+{
+    type Error = EmbeddedDecodeError<R::Error>;
+}
+
+impl<R: Read> Read for EmbeddedDecodeReader<R>
+where
+    R::Error: 'static,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut encoded = [0; 6 * CHUNK_WORDS];
+        while !buf.is_empty() {
+            let chunk = &mut encoded[..buf.len().min(6 * CHUNK_WORDS)];
+            let read = self.inner.read(chunk).map_err(EmbeddedDecodeError::Io)?;
+            let len = self.decode(&chunk[..read], buf)?;
+            if len > 0 || read == 0 {
+                return Ok(len);
+            }
+        }
+        Ok(0)
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<R: embedded_io_async::Read> embedded_io_async::Read for EmbeddedDecodeReader<R>
+where
+    R::Error: 'static,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut encoded = [0; 6 * CHUNK_WORDS];
+        while !buf.is_empty() {
+            let chunk = &mut encoded[..buf.len().min(6 * CHUNK_WORDS)];
+            let read = self
+                .inner
+                .read(chunk)
+                .await
+                .map_err(EmbeddedDecodeError::Io)?;
+            let len = self.decode(&chunk[..read], buf)?;
+            if len > 0 || read == 0 {
+                return Ok(len);
+            }
+        }
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::testing::data;
+
+    /// A reader and writer that moves at most `step` bytes per call.
+    struct Trickle {
+        data: Vec<u8>,
+        step: usize,
+    }
+
+    impl ErrorType for Trickle {
+        type Error = Infallible;
+    }
+
+    impl Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+            let len = self.step.min(buf.len()).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data.drain(..len);
+            Ok(len)
+        }
+    }
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Infallible> {
+            let len = self.step.min(buf.len());
+            self.data.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    fn encode_stream(data: &[u8], step: usize) -> Vec<u8> {
+        let mut writer = EmbeddedEncodeWriter::new(Trickle {
+            data: Vec::new(),
+            step: 5,
+        });
+        for chunk in data.chunks(step) {
+            writer.write_all(chunk).unwrap();
+        }
+        writer.finish().unwrap().data
+    }
+
+    fn decode_stream(
+        encoded: &[u8],
+        step: usize,
+    ) -> Result<Vec<u8>, EmbeddedDecodeError<Infallible>> {
+        let mut reader = EmbeddedDecodeReader::new(Trickle {
+            data: encoded.to_vec(),
+            step,
+        });
+        let mut decoded = Vec::new();
+        let mut buf = [0; 7];
+        loop {
+            match reader.read(&mut buf)? {
+                0 => return Ok(decoded),
+                len => decoded.extend_from_slice(&buf[..len]),
+            }
+        }
+    }
+
+    #[test]
+    fn test_writer_matches_encode_slice() {
+        let data = data(6 * CHUNK_WORDS + 3);
+        let mut out = alloc::vec![0; 6 * data.len()];
+        for len in [
+            0,
+            1,
+            2,
+            2 * CHUNK_WORDS - 1,
+            2 * CHUNK_WORDS + 1,
+            data.len(),
+        ] {
+            let data = &data[..len];
+            let encoded_len = crate::encode_slice(data, &mut out).unwrap();
+            let encoded = &out[..encoded_len];
+            for step in [1, 2, 3, 64, 1024] {
+                assert_eq!(encode_stream(data, step), encoded, "len {len} step {step}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_reader_roundtrip() {
+        let data = data(6 * CHUNK_WORDS + 3);
+        let mut out = alloc::vec![0; 6 * data.len()];
+        for len in [
+            0,
+            1,
+            2,
+            2 * CHUNK_WORDS - 1,
+            2 * CHUNK_WORDS + 1,
+            data.len(),
+        ] {
+            let data = &data[..len];
+            let encoded_len = crate::encode_slice(data, &mut out).unwrap();
+            let encoded = &out[..encoded_len];
+            for step in [1, 5, 6, 1024] {
+                assert_eq!(
+                    decode_stream(encoded, step).unwrap(),
+                    data,
+                    "len {len} step {step}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_reader_errors() {
+        let cases: [(&[u8], DecodeError); 5] = [
+            (b"", DecodeError::MalformedTrailer),
+            (
+                b"axigak-nyryk-humil-bosek-sonax",
+                DecodeError::MalformedHeader,
+            ),
+            (
+                b"xigak-nyryk-humil-bosek-sona",
+                DecodeError::MalformedTrailer,
+            ),
+            (b"xigak-nyryk-humil-bosek-sonaxx", DecodeError::Corrupted),
+            (
+                b"xesef-disof-gytuf-katof-movif-baxix",
+                DecodeError::ChecksumMismatch,
+            ),
+        ];
+        for (encoded, expected) in cases {
+            assert_eq!(
+                decode_stream(encoded, 3),
+                Err(EmbeddedDecodeError::Decode(expected)),
+                "{}",
+                core::str::from_utf8(encoded).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_reader_errors_are_sticky() {
+        let mut reader = EmbeddedDecodeReader::new(&b"xigak-nyryk-humil-bosek-sonaxx"[..]);
+        let mut buf = [0; 64];
+        assert_eq!(reader.read(&mut buf), Ok(9));
+        let err = EmbeddedDecodeError::Decode(DecodeError::Corrupted);
+        assert_eq!(reader.read(&mut buf), Err(err));
+        assert_eq!(reader.read(&mut buf), Err(err));
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    mod nonblocking {
+        use alloc::sync::Arc;
+        use alloc::task::Wake;
+        use core::future::Future;
+        use core::pin::pin;
+        use core::task::{Context, Poll, Waker};
+
+        use super::*;
+
+        struct Noop;
+
+        impl Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        /// Poll a future that never waits to completion.
+        fn run<F: Future>(future: F) -> F::Output {
+            let waker = Waker::from(Arc::new(Noop));
+            let mut cx = Context::from_waker(&waker);
+            let mut future = pin!(future);
+            loop {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    return output;
+                }
+            }
+        }
+
+        impl embedded_io_async::Read for Trickle {
+            async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+                Read::read(self, buf)
+            }
+        }
+
+        impl embedded_io_async::Write for Trickle {
+            async fn write(&mut self, buf: &[u8]) -> Result<usize, Infallible> {
+                Write::write(self, buf)
+            }
+
+            async fn flush(&mut self) -> Result<(), Infallible> {
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn test_async_roundtrip() {
+            let data = data(6 * CHUNK_WORDS + 3);
+            let encoded = run(async {
+                let mut writer = EmbeddedEncodeWriter::new(Trickle {
+                    data: Vec::new(),
+                    step: 5,
+                });
+                for chunk in data.chunks(7) {
+                    embedded_io_async::Write::write_all(&mut writer, chunk)
+                        .await
+                        .unwrap();
+                }
+                writer.finish_async().await.unwrap().data
+            });
+            assert_eq!(encoded, encode_stream(&data, 7));
+
+            let decoded = run(async {
+                let mut reader = EmbeddedDecodeReader::new(Trickle {
+                    data: encoded,
+                    step: 5,
+                });
+                let mut decoded = Vec::new();
+                let mut buf = [0; 7];
+                loop {
+                    let len = embedded_io_async::Read::read(&mut reader, &mut buf)
+                        .await
+                        .unwrap();
+                    if len == 0 {
+                        return decoded;
+                    }
+                    decoded.extend_from_slice(&buf[..len]);
+                }
+            });
+            assert_eq!(decoded, data);
+        }
+    }
+}
@@ -1,97 +1,193 @@
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use core::str;
+
+use crate::Checksum;
 
 const VOWELS: [u8; 6] = *b"aeiouy";
 const CONSONANTS: [u8; 16] = *b"bcdfghklmnprstvz";
-const HEADER: &str = "x";
-const TRAILER: &str = "x";
-const SEPARATOR: &str = "-";
-const MID: &str = "x";
+const HEADER: u8 = b'x';
+const TRAILER: u8 = b'x';
+const SEPARATOR: u8 = b'-';
+const MID: u8 = b'x';
 
-#[must_use]
-pub fn inner(data: &[u8]) -> String {
-    if data.is_empty() {
-        return String::from("xexax");
+// A const block that computes an array of 256 consonant pairs.
+//
+// For every byte, the entry holds the consonants that encode its high and low
+// nibbles. These surround the separator in every word.
+const CONSONANT_PAIRS: [[u8; 2]; 256] = {
+    let mut table = [[0_u8; 2]; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        table[byte] = [CONSONANTS[byte >> 4], CONSONANTS[byte & 15]];
+        byte += 1;
     }
+    table
+};
 
-    let mut encoded = String::with_capacity(6 * (data.len() / 2) + 3 + 2);
-    encoded.push_str(HEADER);
-    let mut checksum = 1_u8;
-    let mut chunks = data.chunks_exact(2);
-    while let Some(&[left, right]) = chunks.next() {
-        odd_partial(left, checksum, &mut encoded);
-        let d = (right >> 4) & 15;
-        let e = right & 15;
-        // Panic safety:
-        //
-        // - `d` is constructed with a mask of `0b1111`.
-        // - `CONSONANTS` is a fixed size array with 16 elements.
-        // - Maximum value of `d` is 15.
-        encoded.push(CONSONANTS[d as usize].into());
-        encoded.push_str(SEPARATOR);
-        // Panic safety:
-        //
-        // - `e` is constructed with a mask of `0b1111`.
-        // - `CONSONANTS` is a fixed size array with 16 elements.
-        // - Maximum value of `e` is 15.
-        encoded.push(CONSONANTS[e as usize].into());
-        checksum = ((u16::from(checksum * 5) + u16::from(left) * 7 + u16::from(right)) % 36) as u8;
+// For every checksum state and byte, the vowel-consonant-vowel triple that
+// starts a word.
+static VOWEL_TRIPLES: [[[u8; 3]; 256]; 36] = {
+    let mut table = [[[0_u8; 3]; 256]; 36];
+    let mut checksum = 0;
+    while checksum < 36 {
+        let mut byte = 0;
+        while byte < 256 {
+            table[checksum][byte] = odd_partial(byte as u8, checksum as u8);
+            byte += 1;
+        }
+        checksum += 1;
     }
-    if let [byte] = chunks.remainder() {
-        odd_partial(*byte, checksum, &mut encoded);
-    } else {
-        even_partial(checksum, &mut encoded);
+    table
+};
+
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn inner(data: &[u8]) -> String {
+    let mut encoded = Vec::new();
+    encode_append(data, &mut encoded);
+    ascii_to_string(encoded)
+}
+
+/// Return the bytes of an encoding as a string slice.
+///
+/// # Panics
+///
+/// Panics if `encoded` is not ASCII, which cannot happen for bytes written by
+/// the encoder since they are all in the ASCII encoding alphabet.
+pub fn ascii_to_str(encoded: &[u8]) -> &str {
+    str::from_utf8(encoded).expect("Bubble Babble encodings are ASCII")
+}
+
+/// Return the bytes of an encoding as a string like [`ascii_to_str`].
+///
+/// # Panics
+///
+/// Panics if `encoded` is not ASCII.
+#[cfg(feature = "alloc")]
+pub fn ascii_to_string(encoded: Vec<u8>) -> String {
+    String::from_utf8(encoded).expect("Bubble Babble encodings are ASCII")
+}
+
+/// Encode `data` and append the encoding to `out`.
+#[cfg(feature = "alloc")]
+pub fn encode_append(data: &[u8], out: &mut Vec<u8>) {
+    let start = out.len();
+    out.resize(start + encoded_len(data.len()), 0);
+    encode_into(data, &mut out[start..]);
+}
+
+/// Return the length of the encoding of `len` bytes of data.
+#[inline]
+pub const fn encoded_len(len: usize) -> usize {
+    // Every pair of bytes is a 6 byte word. The header, final 3-tuple, and
+    // trailer add 5 more bytes.
+    6 * (len / 2) + 5
+}
+
+/// Encode `data` into the front of `out` and return the number of bytes
+/// written.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than [`encoded_len`].
+pub fn encode_into(data: &[u8], out: &mut [u8]) -> usize {
+    encode_into_with(data, out, |pairs, words| {
+        write_pairs(pairs, Checksum::initial(), words)
+    })
+}
+
+/// Encode `data` into the front of `out` like [`encode_into`], with a custom
+/// routine for encoding the pairs of bytes in `data`.
+///
+/// `write_words` is called with the pairs of bytes and the region of `out`
+/// for their words, and must return the checksum after the last pair like
+/// [`write_pairs`].
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than [`encoded_len`].
+pub fn encode_into_with<F>(data: &[u8], out: &mut [u8], write_words: F) -> usize
+where
+    F: FnOnce(&[u8], &mut [u8]) -> Checksum,
+{
+    let len = encoded_len(data.len());
+    let out = &mut out[..len];
+    let (pairs, remainder) = data.split_at(data.len() & !1);
+    let tail_start = len - 4;
+
+    out[0] = HEADER;
+    let checksum = write_words(pairs, &mut out[1..tail_start]);
+    out[tail_start..len - 1].copy_from_slice(&tail(remainder.first().copied(), checksum));
+    out[len - 1] = TRAILER;
+    len
+}
+
+/// Write a 6 byte word into `out` for every pair of bytes in `pairs`, starting
+/// from the given checksum state, and return the checksum after the last pair.
+///
+/// `pairs` must have an even length and `out` must be three times as long.
+#[inline]
+pub fn write_pairs(pairs: &[u8], mut checksum: Checksum, out: &mut [u8]) -> Checksum {
+    debug_assert_eq!(pairs.len() % 2, 0);
+    debug_assert_eq!(out.len(), 3 * pairs.len());
+
+    for (pair, word) in pairs.chunks_exact(2).zip(out.chunks_exact_mut(6)) {
+        let (left, right) = (pair[0], pair[1]);
+        let [vowel1, consonant, vowel2] =
+            VOWEL_TRIPLES[usize::from(checksum.value())][usize::from(left)];
+        let [up, down] = CONSONANT_PAIRS[usize::from(right)];
+        word.copy_from_slice(&[vowel1, consonant, vowel2, up, SEPARATOR, down]);
+        checksum = checksum.update_by_table([left, right]);
     }
-    encoded.push_str(TRAILER);
-    encoded
+    checksum
 }
 
+/// Return the final 3-tuple of an encoding.
+///
+/// The final tuple encodes the last byte of odd length data, otherwise it is
+/// the checksum word.
 #[inline]
-fn odd_partial(raw_byte: u8, checksum: u8, buf: &mut String) {
+pub const fn tail(remainder: Option<u8>, checksum: Checksum) -> [u8; 3] {
+    match remainder {
+        Some(byte) => odd_partial(byte, checksum.value()),
+        None => even_partial(checksum.value()),
+    }
+}
+
+const fn odd_partial(raw_byte: u8, checksum: u8) -> [u8; 3] {
     let a = (((raw_byte >> 6) & 3) + checksum) % 6;
     let b = (raw_byte >> 2) & 15;
     let c = ((raw_byte & 3) + checksum / 6) % 6;
     // Panic safety:
     //
-    // - `a` is constructed with mod 6.
+    // - `a` and `c` are constructed with mod 6.
     // - `VOWELS` is a fixed size array with 6 elements.
-    // - Maximum value of `a` is 5.
-    buf.push(VOWELS[a as usize].into());
-    // Panic safety:
-    //
     // - `b` is constructed with a mask of `0b1111`.
     // - `CONSONANTS` is a fixed size array with 16 elements.
-    // - Maximum value of `e` is 15.
-    buf.push(CONSONANTS[b as usize].into());
-    // Panic safety:
-    //
-    // - `c` is constructed with mod 6.
-    // - `VOWELS` is a fixed size array with 6 elements.
-    // - Maximum value of `c` is 5.
-    buf.push(VOWELS[c as usize].into());
+    [
+        VOWELS[a as usize],
+        CONSONANTS[b as usize],
+        VOWELS[c as usize],
+    ]
 }
 
-#[inline]
-fn even_partial(checksum: u8, buf: &mut String) {
+const fn even_partial(checksum: u8) -> [u8; 3] {
     let a = checksum % 6;
-    // let b = 16;
     let c = checksum / 6;
     // Panic safety:
     //
     // - `a` is constructed with mod 6.
+    // - Maximum value of `checksum` is 35 -- see `Checksum`.
+    // - `c` is constructed with divide by 6, so its maximum value is 5.
     // - `VOWELS` is a fixed size array with 6 elements.
-    // - Maximum value of `a` is 5.
-    buf.push(VOWELS[a as usize].into());
-    buf.push_str(MID);
-    // Panic safety:
-    //
-    // - `c` is constructed with divide by 6.
-    // - Maximum value of `checksum` is 36 -- see `encode` loop.
-    // - `VOWELS` is a fixed size array with 6 elements.
-    // - Maximum value of `c` is 5.
-    buf.push(VOWELS[c as usize].into());
+    [VOWELS[a as usize], MID, VOWELS[c as usize]]
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
 
@@ -230,42 +326,88 @@ mod tests {
 
     #[test]
     fn test_odd_partial() {
-        // odd_partial(raw_byte, checksum, buf) appends three characters:
+        // odd_partial(raw_byte, checksum) returns three characters:
         //   a = (((raw_byte >> 6) & 3) + checksum) % 6     -> from VOWELS
         //   b = (raw_byte >> 2) & 15                       -> from CONSONANTS
         //   c = ((raw_byte & 3) + (checksum / 6)) % 6      -> from VOWELS
-        let mut buf = String::new();
 
         // Test with raw_byte = 0 and checksum = 1.
         // a = (0 + 1) % 6 = 1  -> VOWELS[1] = 'e'
         // b = 0                -> CONSONANTS[0] = 'b'
         // c = (0 + 0) % 6 = 0  -> VOWELS[0] = 'a'
-        odd_partial(0, 1, &mut buf);
-        assert_eq!(buf, "eba");
+        assert_eq!(&odd_partial(0, 1), b"eba");
 
-        buf.clear();
         // Test with raw_byte = 255 and checksum = 1.
         // raw_byte 255 (binary 11111111):
         //   a = (((255 >> 6) & 3) + 1) % 6 = ((3 + 1) % 6) = 4     -> VOWELS[4] = 'u'
         //   b = (255 >> 2) & 15 = (63 & 15) = 15                   -> CONSONANTS[15] = 'z'
         //   c = ((255 & 3) + (1/6)) % 6 = (3 + 0) % 6 = 3          -> VOWELS[3] = 'o'
-        odd_partial(255, 1, &mut buf);
-        assert_eq!(buf, "uzo");
+        assert_eq!(&odd_partial(255, 1), b"uzo");
     }
 
     #[test]
     fn test_even_partial() {
-        // even_partial(checksum, buf) appends three characters:
+        // even_partial(checksum) returns three characters:
         //   a = checksum % 6       -> from VOWELS
         //   MID (a literal "x")
         //   c = checksum / 6       -> from VOWELS
-        let mut buf = String::new();
 
         // For checksum = 5:
         //   a = 5 % 6 = 5      -> VOWELS[5] = 'y'
         //   c = 5 / 6 = 0      -> VOWELS[0] = 'a'
-        // So even_partial(5) should append "yxa".
-        even_partial(5, &mut buf);
-        assert_eq!(buf, "yxa");
+        // So even_partial(5) should return "yxa".
+        assert_eq!(&even_partial(5), b"yxa");
+    }
+
+    #[test]
+    fn test_tables() {
+        for byte in 0..=u8::MAX {
+            let [high, low] = CONSONANT_PAIRS[usize::from(byte)];
+            assert_eq!(high, CONSONANTS[usize::from(byte >> 4)]);
+            assert_eq!(low, CONSONANTS[usize::from(byte & 15)]);
+            for checksum in 0..36 {
+                assert_eq!(
+                    VOWEL_TRIPLES[usize::from(checksum)][usize::from(byte)],
+                    odd_partial(byte, checksum)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_pairs_returns_checksum() {
+        let mut out = [0; 12];
+        let checksum = write_pairs(&[0, 0, 0, 0], Checksum::initial(), &mut out);
+        assert_eq!(checksum.value(), 25);
+        assert_eq!(&out, b"ebab-bybab-b");
+        let checksum = Checksum::from_value(17).unwrap();
+        assert_eq!(write_pairs(&[], checksum, &mut []), checksum);
+    }
+
+    #[test]
+    fn test_encode_into_larger_buffer() {
+        let mut out = [b'?'; 40];
+        let len = encode_into(b"Pineapple", &mut out);
+        assert_eq!(len, encoded_len(9));
+        assert_eq!(&out[..len], b"xigak-nyryk-humil-bosek-sonax");
+        assert!(out[len..].iter().all(|&byte| byte == b'?'));
+    }
+
+    #[test]
+    fn test_encode_slice_output_sizes() {
+        let encoded = b"xigak-nyryk-humil-bosek-sonax";
+        for len in 0..=encoded.len() + 2 {
+            let mut out = [b'?'; 32];
+            let result = crate::encode_slice("Pineapple", &mut out[..len]);
+            if len < encoded.len() {
+                assert_eq!(result, Err(crate::EncodeSliceError::OutputTooSmall));
+                assert!(out.iter().all(|&byte| byte == b'?'));
+            } else {
+                assert_eq!(result, Ok(encoded.len()));
+                assert_eq!(&out[..encoded.len()], encoded);
+            }
+        }
+        assert_eq!(crate::encoded_len(usize::MAX), None);
+        assert_eq!(crate::encoded_len(9), Some(encoded_len(9)));
     }
 }
@@ -0,0 +1,214 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::decode::decode_state;
+use crate::encode::{ascii_to_string, tail, write_pairs};
+use crate::{Checksum, DecodeError};
+
+/// An incremental Bubble Babble encoder.
+///
+/// Data is appended with [`update`](Self::update) and the encoding of all the
+/// data so far is returned by [`finish`](Self::finish). The encoder is not
+/// consumed by `finish`, so it can be used to maintain the encoding of an
+/// append-only log as it grows.
+///
+/// The final word of an encoding holds the running checksum and the last byte
+/// of odd length data, so an encoder can also be [resumed](Self::resume) from
+/// an existing encoding without the data it encodes.
+///
+/// # Examples
+///
+/// ```
+/// use boba::Encoder;
+///
+/// let mut encoder = Encoder::resume("xigak-nyryk-humil-bosek-sonax")?;
+/// encoder.update(" pizza");
+/// assert_eq!(encoder.finish(), boba::encode("Pineapple pizza"));
+/// # Ok::<(), boba::DecodeError>(())
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Encoder {
+    // The words of every full pair of bytes, without the header.
+    words: Vec<u8>,
+    checksum: Checksum,
+    // The last byte of odd length data, which is not yet part of a word.
+    pending: Option<u8>,
+}
+
+impl Encoder {
+    /// Create a new encoder for empty data.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            checksum: Checksum::initial(),
+            pending: None,
+        }
+    }
+
+    /// Create a new encoder for empty data with room for the encoding of `len`
+    /// bytes of data.
+    #[must_use]
+    pub fn with_capacity(len: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(3 * (len & !1)),
+            ..Self::new()
+        }
+    }
+
+    /// Create an encoder that continues an existing encoding.
+    ///
+    /// The encoding is validated and the checksum and last byte of odd length
+    /// data are recovered from its final word. Appending `more` to the
+    /// resumed encoder produces the encoding of the original data followed by
+    /// `more`.
+    ///
+    /// # Errors
+    ///
+    /// If `encoded` is not a valid encoding, the error reported by
+    /// [`decode`](crate::decode()) is returned. Encodings with a word missing
+    /// its `-` separator are not produced by [`encode`](crate::encode()) and
+    /// are rejected with [`DecodeError::Corrupted`].
+    pub fn resume<T: AsRef<[u8]>>(encoded: T) -> Result<Self, DecodeError> {
+        let encoded = encoded.as_ref();
+        let (checksum, pending) = decode_state(encoded)?;
+        // Strip the header and the final 3-tuple and trailer.
+        let words = encoded[1..encoded.len() - 4].to_vec();
+        Ok(Self {
+            words,
+            checksum,
+            pending,
+        })
+    }
+
+    /// Append `data` to the encoded data.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        let mut data = data.as_ref();
+        if let Some(left) = self.pending {
+            let Some((&right, rest)) = data.split_first() else {
+                return;
+            };
+            self.write_pairs(&[left, right]);
+            data = rest;
+        }
+        let (pairs, remainder) = data.split_at(data.len() & !1);
+        self.write_pairs(pairs);
+        self.pending = remainder.first().copied();
+    }
+
+    /// Return the encoding of all data appended so far.
+    ///
+    /// More data may be appended after calling `finish`.
+    #[must_use]
+    pub fn finish(&self) -> String {
+        let mut encoded = Vec::with_capacity(self.words.len() + 5);
+        encoded.push(b'x');
+        encoded.extend_from_slice(&self.words);
+        encoded.extend_from_slice(&tail(self.pending, self.checksum));
+        encoded.push(b'x');
+        ascii_to_string(encoded)
+    }
+
+    /// Return the running checksum after the last full pair of bytes.
+    #[must_use]
+    pub fn checksum(&self) -> Checksum {
+        self.checksum
+    }
+
+    /// Return the number of bytes of data encoded so far.
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.words.len() / 3 + usize::from(self.pending.is_some())
+    }
+
+    fn write_pairs(&mut self, pairs: &[u8]) {
+        let start = self.words.len();
+        self.words.resize(start + 3 * pairs.len(), 0);
+        self.checksum = write_pairs(pairs, self.checksum, &mut self.words[start..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_update_matches_encode() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        for split in 0..=data.len() {
+            let (head, tail) = data.split_at(split);
+            let mut encoder = Encoder::new();
+            encoder.update(head);
+            assert_eq!(encoder.finish(), crate::encode(head));
+            encoder.update(tail);
+            assert_eq!(encoder.finish(), crate::encode(data), "split at {split}");
+            assert_eq!(encoder.data_len(), data.len());
+            assert_eq!(encoder.checksum(), Checksum::from_bytes(data));
+        }
+
+        let mut encoder = Encoder::new();
+        for byte in data {
+            encoder.update([*byte]);
+            encoder.update([]);
+        }
+        assert_eq!(encoder.finish(), crate::encode(data));
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut encoder = Encoder::with_capacity(9);
+        assert_eq!(encoder, Encoder::new());
+        for byte in b"Pineapple" {
+            encoder.update([*byte]);
+        }
+        assert_eq!(encoder.words.capacity(), 24);
+        assert_eq!(encoder.finish(), "xigak-nyryk-humil-bosek-sonax");
+    }
+
+    #[test]
+    fn test_resume_matches_encode() {
+        let data = (0..=255).chain((0..=255).rev()).collect::<Vec<u8>>();
+        for split in 0..=64 {
+            let (head, tail) = data.split_at(split);
+            let mut encoder = Encoder::resume(crate::encode(head)).unwrap();
+            assert_eq!(encoder.data_len(), head.len());
+            encoder.update(tail);
+            assert_eq!(encoder.finish(), crate::encode(&data), "split at {split}");
+        }
+        let mut encoder = Encoder::resume("xexax").unwrap();
+        assert_eq!(encoder, Encoder::new());
+        encoder.update(&data);
+        assert_eq!(encoder.finish(), crate::encode(&data));
+    }
+
+    #[test]
+    fn test_resume_invalid_encoding() {
+        let cases: [&[u8]; 7] = [
+            b"",
+            b"xigak-nyryk-humil-bosek-sonay",
+            b"yigak-nyryk-humil-bosek-sonax",
+            b"xigak-nyryk-humil-bosek-sonaxx",
+            b"xigak-nyryk-hu!il-bosek-sonax",
+            b"xigak-nyryk-humil-bosek-sonox",
+            b"xigak-nyryk-humil-bosek-soax",
+        ];
+        for encoded in cases {
+            assert_eq!(
+                Encoder::resume(encoded),
+                Err(crate::decode(encoded).unwrap_err()),
+                "{encoded:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_resume_rejects_missing_separator() {
+        // The decoder skips a word without a separator and decodes this as
+        // empty data, which is encoded as `xexax`.
+        let encoded = b"xebabxbexax";
+        assert_eq!(crate::decode(encoded), Ok(Vec::new()));
+        assert_eq!(Encoder::resume(encoded), Err(DecodeError::Corrupted));
+    }
+}
@@ -0,0 +1,212 @@
+//! Bubble Babble fingerprints with built-in hashes.
+//!
+//! This module has small, self-contained MD5, SHA-1 and SHA-256
+//! implementations for producing fingerprints like those printed by
+//! `ssh-keygen -B` without depending on a hashing library. Each hash is
+//! enabled by the Cargo feature of the same name. The hashers work without an
+//! allocator. The fingerprint functions, which return the hash in the Bubble
+//! Babble encoding, also need the **alloc** feature.
+//!
+//! The fingerprints match the output of Ruby's `Digest::MD5.bubblebabble`,
+//! `Digest::SHA1.bubblebabble` and `Digest::SHA256.bubblebabble`.
+//!
+//! To fingerprint with other hashes, see the **digest** feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(all(feature = "sha256", feature = "alloc"))]
+//! # {
+//! use boba::fingerprint;
+//!
+//! assert_eq!(
+//!     fingerprint::sha256("Pineapple"),
+//!     "xobag-deleb-bymes-hocud-lisem-pobup-muhus-metil-harih-simot-fecec-varub-damyb-gyvaf-bokul-hovac-raxux"
+//! );
+//! # }
+//! ```
+//!
+//! Hash data incrementally without an allocator:
+//!
+//! ```
+//! # #[cfg(feature = "sha1")]
+//! # {
+//! use boba::fingerprint::Sha1;
+//! use boba::BubbleBabbleDisplay;
+//!
+//! let mut hasher = Sha1::new();
+//! hasher.update("Pine");
+//! hasher.update("apple");
+//! let hash = hasher.finalize();
+//! let fingerprint = BubbleBabbleDisplay::new(&hash);
+//! # assert_eq!(
+//! #     fingerprint.to_string(),
+//! #     "xetal-pirot-higov-sypos-hebur-denyv-legav-cepuv-tyrom-cetaz-muxyx"
+//! # );
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "md5")]
+#[cfg_attr(docsrs, doc(cfg(feature = "md5")))]
+pub use crate::hashes::Md5;
+#[cfg(feature = "sha1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sha1")))]
+pub use crate::hashes::Sha1;
+#[cfg(feature = "sha256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sha256")))]
+pub use crate::hashes::Sha256;
+
+/// A built-in hash for fingerprints.
+///
+/// Each variant is enabled by the Cargo feature of the same name.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "sha256", feature = "alloc"))]
+/// # {
+/// use boba::fingerprint::{self, Algorithm};
+///
+/// assert_eq!(
+///     Algorithm::Sha256.fingerprint("Pineapple"),
+///     fingerprint::sha256("Pineapple")
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// MD5, with 16 byte hashes.
+    #[cfg(feature = "md5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "md5")))]
+    Md5,
+    /// SHA-1, with 20 byte hashes.
+    #[cfg(feature = "sha1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sha1")))]
+    Sha1,
+    /// SHA-256, with 32 byte hashes.
+    #[cfg(feature = "sha256")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sha256")))]
+    Sha256,
+}
+
+impl Algorithm {
+    /// Return the Bubble Babble encoding of the hash of `data`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[must_use]
+    pub fn fingerprint<T: AsRef<[u8]>>(self, data: T) -> String {
+        match self {
+            #[cfg(feature = "md5")]
+            Self::Md5 => md5(data),
+            #[cfg(feature = "sha1")]
+            Self::Sha1 => sha1(data),
+            #[cfg(feature = "sha256")]
+            Self::Sha256 => sha256(data),
+        }
+    }
+}
+
+/// Return the Bubble Babble encoding of the MD5 hash of `data`.
+///
+/// # Examples
+///
+/// ```
+/// use boba::fingerprint;
+///
+/// assert_eq!(
+///     fingerprint::md5(""),
+///     "xuhac-tifet-nyfob-busob-gopam-beden-meraz-mebul-vaxax"
+/// );
+/// ```
+#[cfg(all(feature = "md5", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "md5", feature = "alloc"))))]
+#[must_use]
+pub fn md5<T: AsRef<[u8]>>(data: T) -> String {
+    crate::encode(Md5::digest(data))
+}
+
+/// Return the Bubble Babble encoding of the SHA-1 hash of `data`.
+///
+/// # Examples
+///
+/// ```
+/// use boba::fingerprint;
+///
+/// assert_eq!(
+///     fingerprint::sha1(""),
+///     "xukif-namov-vilek-rodab-tisah-hozev-zyhik-bikyn-barot-mocab-nexox"
+/// );
+/// ```
+#[cfg(all(feature = "sha1", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "sha1", feature = "alloc"))))]
+#[must_use]
+pub fn sha1<T: AsRef<[u8]>>(data: T) -> String {
+    crate::encode(Sha1::digest(data))
+}
+
+/// Return the Bubble Babble encoding of the SHA-256 hash of `data`.
+///
+/// # Examples
+///
+/// ```
+/// use boba::fingerprint;
+///
+/// assert_eq!(
+///     fingerprint::sha256(""),
+///     "xumor-boceg-dakuz-sulic-gukoz-rutas-mekek-zovud-gunap-vabov-genin-rygyg-sanun-hykac-ruvah-dovah-huxex"
+/// );
+/// ```
+#[cfg(all(feature = "sha256", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "sha256", feature = "alloc"))))]
+#[must_use]
+pub fn sha256<T: AsRef<[u8]>>(data: T) -> String {
+    crate::encode(Sha256::digest(data))
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    // The outputs of Ruby's `Digest::MD5.bubblebabble`,
+    // `Digest::SHA1.bubblebabble` and `Digest::SHA256.bubblebabble`.
+
+    #[test]
+    #[cfg(feature = "md5")]
+    fn test_md5() {
+        assert_eq!(
+            super::md5("abc"),
+            "xogab-cegen-muzot-dofar-buhin-kozil-typev-cozal-dyxox"
+        );
+        assert_eq!(
+            super::md5("Pineapple"),
+            "xivah-hofyp-cytud-pyron-dykin-pimyp-ziseb-cugof-gyxyx"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sha1")]
+    fn test_sha1() {
+        assert_eq!(
+            super::sha1("abc"),
+            "xopen-nozof-kaceb-kibek-povif-venel-cavih-babek-selet-bikon-tixox"
+        );
+        assert_eq!(
+            super::sha1("Pineapple"),
+            "xetal-pirot-higov-sypos-hebur-denyv-legav-cepuv-tyrom-cetaz-muxyx"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sha256")]
+    fn test_sha256() {
+        assert_eq!(
+            super::sha256("abc"),
+            "xovil-myhyr-zafeb-cyfev-pibog-cubet-vilap-vimod-fosub-fymyp-fahoc-livon-sotac-bazak-cusob-behep-texax"
+        );
+        assert_eq!(
+            super::sha256("Pineapple"),
+            "xobag-deleb-bymes-hocud-lisem-pobup-muhus-metil-harih-simot-fecec-varub-damyb-gyvaf-bokul-hovac-raxux"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::decode::decode_slice;
+use crate::encode::{ascii_to_str, encode_into};
+use crate::{encoded_len, max_decoded_len, DecodeSliceError, EncodeSliceError};
+
+/// Return the length of the encoding of `data` if it fits in `capacity`
+/// bytes.
+fn checked_encoded_len(data: &[u8], capacity: usize) -> Result<usize, EncodeSliceError> {
+    match encoded_len(data.len()) {
+        Some(len) if len <= capacity => Ok(len),
+        _ => Err(EncodeSliceError::OutputTooSmall),
+    }
+}
+
+/// Return the length of the buffer to decode `encoded` into with at most
+/// `capacity` bytes.
+fn decode_buffer_len(encoded: &[u8], capacity: usize) -> usize {
+    max_decoded_len(encoded.len()).min(capacity)
+}
+
+#[cfg(feature = "heapless")]
+pub fn encode_heapless<const N: usize>(
+    data: &[u8],
+) -> Result<heapless::String<N>, EncodeSliceError> {
+    let len = checked_encoded_len(data, N)?;
+    let mut encoded = heapless::Vec::<u8, N>::new();
+    encoded
+        .resize(len, 0)
+        .map_err(|()| EncodeSliceError::OutputTooSmall)?;
+    encode_into(data, &mut encoded);
+    let mut out = heapless::String::new();
+    out.push_str(ascii_to_str(&encoded))
+        .expect("the encoding fits in the capacity");
+    Ok(out)
+}
+
+#[cfg(feature = "heapless")]
+pub fn decode_heapless<const N: usize>(
+    encoded: &[u8],
+) -> Result<heapless::Vec<u8, N>, DecodeSliceError> {
+    let mut decoded = heapless::Vec::<u8, N>::new();
+    decoded
+        .resize(decode_buffer_len(encoded, N), 0)
+        .map_err(|()| DecodeSliceError::OutputTooSmall)?;
+    let len = decode_slice(encoded, &mut decoded)?;
+    decoded.truncate(len);
+    Ok(decoded)
+}
+
+#[cfg(feature = "arrayvec")]
+pub fn encode_arrayvec<const N: usize>(
+    data: &[u8],
+) -> Result<arrayvec::ArrayString<N>, EncodeSliceError> {
+    let len = checked_encoded_len(data, N)?;
+    let mut encoded = arrayvec::ArrayVec::<u8, N>::new();
+    encoded.extend(core::iter::repeat_n(0, len));
+    encode_into(data, &mut encoded);
+    Ok(arrayvec::ArrayString::from(ascii_to_str(&encoded))
+        .expect("the encoding fits in the capacity"))
+}
+
+#[cfg(feature = "arrayvec")]
+pub fn decode_arrayvec<const N: usize>(
+    encoded: &[u8],
+) -> Result<arrayvec::ArrayVec<u8, N>, DecodeSliceError> {
+    let mut decoded = arrayvec::ArrayVec::<u8, N>::new();
+    decoded.extend(core::iter::repeat_n(0, decode_buffer_len(encoded, N)));
+    let len = decode_slice(encoded, &mut decoded)?;
+    decoded.truncate(len);
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::encode_slice;
+
+    const CASES: [(&[u8], &str); 4] = [
+        (b"", "xexax"),
+        (b"1234567890", "xesef-disof-gytuf-katof-movif-baxux"),
+        (b"Pineapple", "xigak-nyryk-humil-bosek-sonax"),
+        (b"xyz!x6", "xival-neved-cavuf-kexyx"),
+    ];
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_heapless() {
+        for (data, encoded) in CASES {
+            let actual = encode_heapless::<64>(data).unwrap();
+            assert_eq!(actual, encoded);
+            let actual = decode_heapless::<16>(encoded.as_bytes()).unwrap();
+            assert_eq!(actual, data);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_heapless_capacity() {
+        // "Pineapple" encodes to 29 bytes.
+        assert!(encode_heapless::<29>(b"Pineapple").is_ok());
+        assert_eq!(
+            encode_heapless::<28>(b"Pineapple"),
+            Err(EncodeSliceError::OutputTooSmall)
+        );
+        assert!(encode_heapless::<0>(b"").is_err());
+
+        let encoded = b"xigak-nyryk-humil-bosek-sonax";
+        assert!(decode_heapless::<9>(encoded).is_ok());
+        assert_eq!(
+            decode_heapless::<8>(encoded),
+            Err(DecodeSliceError::OutputTooSmall)
+        );
+        assert_eq!(
+            decode_heapless::<16>(b"x789x"),
+            Err(DecodeSliceError::Decode(crate::DecodeError::InvalidByte(1)))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn test_arrayvec() {
+        for (data, encoded) in CASES {
+            let actual = encode_arrayvec::<64>(data).unwrap();
+            assert_eq!(actual.as_str(), encoded);
+            let actual = decode_arrayvec::<16>(encoded.as_bytes()).unwrap();
+            assert_eq!(actual.as_slice(), data);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn test_arrayvec_capacity() {
+        // "Pineapple" encodes to 29 bytes.
+        assert!(encode_arrayvec::<29>(b"Pineapple").is_ok());
+        assert_eq!(
+            encode_arrayvec::<28>(b"Pineapple"),
+            Err(EncodeSliceError::OutputTooSmall)
+        );
+        assert!(encode_arrayvec::<0>(b"").is_err());
+
+        let encoded = b"xigak-nyryk-humil-bosek-sonax";
+        assert!(decode_arrayvec::<9>(encoded).is_ok());
+        assert_eq!(
+            decode_arrayvec::<8>(encoded),
+            Err(DecodeSliceError::OutputTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_matches_encode_slice() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        let mut out = vec![0; 1024];
+        for len in 0..=data.len() {
+            let data = &data[..len];
+            let len = encode_slice(data, &mut out).unwrap();
+            let encoded = &out[..len];
+            #[cfg(feature = "heapless")]
+            {
+                assert_eq!(encode_heapless::<1024>(data).unwrap().as_bytes(), encoded);
+                assert_eq!(decode_heapless::<256>(encoded).unwrap(), data);
+            }
+            #[cfg(feature = "arrayvec")]
+            {
+                assert_eq!(encode_arrayvec::<1024>(data).unwrap().as_bytes(), encoded);
+                assert_eq!(decode_arrayvec::<256>(encoded).unwrap().as_slice(), data);
+            }
+        }
+    }
+}
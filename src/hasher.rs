@@ -0,0 +1,271 @@
+use alloc::string::String;
+
+use digest::{Digest, FixedOutputReset, Reset, Update};
+
+/// Bubble Babble output for any [`Digest`] hasher.
+///
+/// This trait is implemented for every [`Digest`], like the hashers of the
+/// [RustCrypto] project. Its methods return the hash in the Bubble Babble
+/// encoding, like Ruby's `Digest::Instance#bubblebabble` and Perl's
+/// `Digest::BubbleBabble`.
+///
+/// [RustCrypto]: https://github.com/RustCrypto/hashes
+///
+/// # Examples
+///
+/// ```
+/// use boba::DigestExt;
+/// use sha2::digest::Update;
+/// use sha2::{Digest, Sha256};
+///
+/// let fingerprint = Sha256::new().chain(b"Pine").chain(b"apple").finalize_bubblebabble();
+/// assert_eq!(fingerprint, Sha256::digest_bubblebabble(b"Pineapple"));
+/// assert!(fingerprint.starts_with("xobag-deleb-bymes-"));
+/// ```
+pub trait DigestExt: Digest {
+    /// Return the hash of the data fed to the hasher in the Bubble Babble
+    /// encoding.
+    #[must_use]
+    fn finalize_bubblebabble(self) -> String;
+
+    /// Return the hash of the data fed to the hasher in the Bubble Babble
+    /// encoding and reset the hasher to its initial state.
+    #[must_use]
+    fn finalize_bubblebabble_reset(&mut self) -> String
+    where
+        Self: FixedOutputReset;
+
+    /// Hash `data` and return the hash in the Bubble Babble encoding.
+    ///
+    /// This is the equivalent of Ruby's `Digest::SHA256.bubblebabble(data)`
+    /// class method.
+    #[must_use]
+    fn digest_bubblebabble(data: impl AsRef<[u8]>) -> String;
+}
+
+impl<D: Digest> DigestExt for D {
+    fn finalize_bubblebabble(self) -> String {
+        crate::encode(self.finalize())
+    }
+
+    fn finalize_bubblebabble_reset(&mut self) -> String
+    where
+        Self: FixedOutputReset,
+    {
+        crate::encode(Digest::finalize_reset(self))
+    }
+
+    fn digest_bubblebabble(data: impl AsRef<[u8]>) -> String {
+        crate::encode(D::digest(data))
+    }
+}
+
+/// A [`Digest`] hasher that returns its hash in the Bubble Babble encoding.
+///
+/// `BubbleBabbleDigest` feeds data to the wrapped hasher and encodes the hash
+/// when it is finalized, so code that displays fingerprints can name the
+/// hasher once and pass the wrapper around. Use the [`DigestExt`] methods to
+/// encode the hash of an existing hasher instead.
+///
+/// With the **std** feature, `BubbleBabbleDigest` is a [`Write`] sink, so
+/// files and other readers can be hashed with [`std::io::copy`].
+///
+/// [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+/// [`std::io::copy`]: https://doc.rust-lang.org/std/io/fn.copy.html
+///
+/// # Examples
+///
+/// ```
+/// use boba::BubbleBabbleDigest;
+/// use sha2::Sha256;
+///
+/// let mut hasher = BubbleBabbleDigest::<Sha256>::new();
+/// hasher.update(b"Pine");
+/// hasher.update(b"apple");
+/// assert_eq!(
+///     hasher.finalize(),
+///     "xobag-deleb-bymes-hocud-lisem-pobup-muhus-metil-harih-simot-fecec-varub-damyb-gyvaf-bokul-hovac-raxux"
+/// );
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct BubbleBabbleDigest<D> {
+    inner: D,
+}
+
+impl<D: Digest> BubbleBabbleDigest<D> {
+    /// Create a hasher in its initial state.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { inner: D::new() }
+    }
+
+    /// Hash `data` and return the hash in the Bubble Babble encoding.
+    #[must_use]
+    pub fn digest(data: impl AsRef<[u8]>) -> String {
+        D::digest_bubblebabble(data)
+    }
+
+    /// Feed `data` to the hasher.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        Digest::update(&mut self.inner, data);
+    }
+
+    /// Feed `data` to the hasher in a chained manner.
+    #[must_use]
+    pub fn chain_update(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.update(data);
+        self
+    }
+
+    /// Return the hash of the data fed to the hasher in the Bubble Babble
+    /// encoding.
+    #[must_use]
+    pub fn finalize(self) -> String {
+        self.inner.finalize_bubblebabble()
+    }
+
+    /// Return the hash of the data fed to the hasher in the Bubble Babble
+    /// encoding and reset the hasher to its initial state.
+    #[must_use]
+    pub fn finalize_reset(&mut self) -> String
+    where
+        D: FixedOutputReset,
+    {
+        self.inner.finalize_bubblebabble_reset()
+    }
+
+    /// Reset the hasher to its initial state.
+    pub fn reset(&mut self)
+    where
+        D: Reset,
+    {
+        Digest::reset(&mut self.inner);
+    }
+
+    /// Return a reference to the wrapped hasher.
+    pub fn get_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Return the wrapped hasher.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D: Digest> From<D> for BubbleBabbleDigest<D> {
+    /// Wrap a hasher, keeping the data already fed to it.
+    fn from(inner: D) -> Self {
+        Self { inner }
+    }
+}
+
+impl<D: Update> Update for BubbleBabbleDigest<D> {
+    fn update(&mut self, data: &[u8]) {
+        Update::update(&mut self.inner, data);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: Update> std::io::Write for BubbleBabbleDigest<D> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Update::update(&mut self.inner, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use md5::Md5;
+    use sha1::Sha1;
+    use sha2::Sha256;
+
+    use super::*;
+
+    // The outputs of Ruby's `Digest::MD5.bubblebabble`,
+    // `Digest::SHA1.bubblebabble` and `Digest::SHA256.bubblebabble`.
+    const MD5: [(&[u8], &str); 3] = [
+        (b"", "xuhac-tifet-nyfob-busob-gopam-beden-meraz-mebul-vaxax"),
+        (
+            b"abc",
+            "xogab-cegen-muzot-dofar-buhin-kozil-typev-cozal-dyxox",
+        ),
+        (
+            b"Pineapple",
+            "xivah-hofyp-cytud-pyron-dykin-pimyp-ziseb-cugof-gyxyx",
+        ),
+    ];
+    const SHA1: [(&[u8], &str); 3] = [
+        (
+            b"",
+            "xukif-namov-vilek-rodab-tisah-hozev-zyhik-bikyn-barot-mocab-nexox",
+        ),
+        (
+            b"abc",
+            "xopen-nozof-kaceb-kibek-povif-venel-cavih-babek-selet-bikon-tixox",
+        ),
+        (
+            b"Pineapple",
+            "xetal-pirot-higov-sypos-hebur-denyv-legav-cepuv-tyrom-cetaz-muxyx",
+        ),
+    ];
+    const SHA256: [(&[u8], &str); 3] = [
+        (b"", "xumor-boceg-dakuz-sulic-gukoz-rutas-mekek-zovud-gunap-vabov-genin-rygyg-sanun-hykac-ruvah-dovah-huxex"),
+        (b"abc", "xovil-myhyr-zafeb-cyfev-pibog-cubet-vilap-vimod-fosub-fymyp-fahoc-livon-sotac-bazak-cusob-behep-texax"),
+        (b"Pineapple", "xobag-deleb-bymes-hocud-lisem-pobup-muhus-metil-harih-simot-fecec-varub-damyb-gyvaf-bokul-hovac-raxux"),
+    ];
+
+    fn check<D: Digest + Update + FixedOutputReset + Clone>(vectors: &[(&[u8], &str)]) {
+        for &(data, expected) in vectors {
+            assert_eq!(D::digest_bubblebabble(data), expected);
+            assert_eq!(D::new().chain(data).finalize_bubblebabble(), expected);
+            assert_eq!(BubbleBabbleDigest::<D>::digest(data), expected);
+
+            let mut hasher = BubbleBabbleDigest::<D>::new();
+            for byte in data.chunks(1) {
+                hasher.update(byte);
+            }
+            assert_eq!(hasher.clone().finalize(), expected);
+            assert_eq!(hasher.finalize_reset(), expected);
+            assert_eq!(hasher.chain_update(data).finalize(), expected);
+        }
+    }
+
+    #[test]
+    fn test_md5() {
+        check::<Md5>(&MD5);
+    }
+
+    #[test]
+    fn test_sha1() {
+        check::<Sha1>(&SHA1);
+    }
+
+    #[test]
+    fn test_sha256() {
+        check::<Sha256>(&SHA256);
+    }
+
+    #[test]
+    fn test_finalize_reset() {
+        let mut hasher = Sha256::new_with_prefix(b"abc");
+        assert_eq!(hasher.finalize_bubblebabble_reset(), SHA256[1].1);
+        assert_eq!(hasher.finalize_bubblebabble(), SHA256[0].1);
+
+        let mut hasher = BubbleBabbleDigest::from(Sha256::new_with_prefix(b"abc"));
+        hasher.reset();
+        assert_eq!(hasher.finalize(), SHA256[0].1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_write() {
+        let mut hasher = BubbleBabbleDigest::<Sha256>::new();
+        std::io::copy(&mut &b"Pineapple"[..], &mut hasher).unwrap();
+        assert_eq!(hasher.finalize(), SHA256[2].1);
+    }
+}
@@ -0,0 +1,679 @@
+/// The length of the blocks processed by the MD5, SHA-1 and SHA-256
+/// compression functions.
+const BLOCK_LEN: usize = 64;
+
+/// Splits a message into blocks for a compression function and pads the last
+/// block with the message length.
+#[derive(Debug, Clone)]
+struct Blocks {
+    buf: [u8; BLOCK_LEN],
+    // The number of bytes in `buf` that are not yet compressed.
+    len: usize,
+    // The length of the message in bytes.
+    total: u64,
+}
+
+impl Blocks {
+    const fn new() -> Self {
+        Self {
+            buf: [0; BLOCK_LEN],
+            len: 0,
+            total: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8], mut compress: impl FnMut(&[u8; BLOCK_LEN])) {
+        self.total = self.total.wrapping_add(data.len() as u64);
+        if self.len > 0 {
+            let take = data.len().min(BLOCK_LEN - self.len);
+            let (head, rest) = data.split_at(take);
+            self.buf[self.len..self.len + take].copy_from_slice(head);
+            self.len += take;
+            data = rest;
+            if self.len < BLOCK_LEN {
+                return;
+            }
+            compress(&self.buf);
+            self.len = 0;
+        }
+        let mut blocks = data.chunks_exact(BLOCK_LEN);
+        for block in &mut blocks {
+            self.buf.copy_from_slice(block);
+            compress(&self.buf);
+        }
+        let rest = blocks.remainder();
+        self.buf[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+    }
+
+    /// Return the length of the message in bits, wrapped like the padding
+    /// of all three hashes requires.
+    const fn bit_len(&self) -> u64 {
+        self.total.wrapping_mul(8)
+    }
+
+    fn finish(&mut self, bit_len: [u8; 8], mut compress: impl FnMut(&[u8; BLOCK_LEN])) {
+        self.buf[self.len] = 0x80;
+        self.buf[self.len + 1..].fill(0);
+        if self.len >= BLOCK_LEN - 8 {
+            compress(&self.buf);
+            self.buf.fill(0);
+        }
+        self.buf[BLOCK_LEN - 8..].copy_from_slice(&bit_len);
+        compress(&self.buf);
+    }
+}
+
+fn words<const LE: bool>(block: &[u8; BLOCK_LEN]) -> [u32; 16] {
+    let mut words = [0; 16];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        *word = if LE {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        };
+    }
+    words
+}
+
+#[cfg(feature = "md5")]
+const MD5_INITIAL: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+#[cfg(feature = "md5")]
+const MD5_SHIFTS: [[u32; 4]; 4] = [
+    [7, 12, 17, 22],
+    [5, 9, 14, 20],
+    [4, 11, 16, 23],
+    [6, 10, 15, 21],
+];
+
+#[cfg(feature = "md5")]
+const MD5_K: [u32; 64] = [
+    0xd76a_a478,
+    0xe8c7_b756,
+    0x2420_70db,
+    0xc1bd_ceee,
+    0xf57c_0faf,
+    0x4787_c62a,
+    0xa830_4613,
+    0xfd46_9501,
+    0x6980_98d8,
+    0x8b44_f7af,
+    0xffff_5bb1,
+    0x895c_d7be,
+    0x6b90_1122,
+    0xfd98_7193,
+    0xa679_438e,
+    0x49b4_0821,
+    0xf61e_2562,
+    0xc040_b340,
+    0x265e_5a51,
+    0xe9b6_c7aa,
+    0xd62f_105d,
+    0x0244_1453,
+    0xd8a1_e681,
+    0xe7d3_fbc8,
+    0x21e1_cde6,
+    0xc337_07d6,
+    0xf4d5_0d87,
+    0x455a_14ed,
+    0xa9e3_e905,
+    0xfcef_a3f8,
+    0x676f_02d9,
+    0x8d2a_4c8a,
+    0xfffa_3942,
+    0x8771_f681,
+    0x6d9d_6122,
+    0xfde5_380c,
+    0xa4be_ea44,
+    0x4bde_cfa9,
+    0xf6bb_4b60,
+    0xbebf_bc70,
+    0x289b_7ec6,
+    0xeaa1_27fa,
+    0xd4ef_3085,
+    0x0488_1d05,
+    0xd9d4_d039,
+    0xe6db_99e5,
+    0x1fa2_7cf8,
+    0xc4ac_5665,
+    0xf429_2244,
+    0x432a_ff97,
+    0xab94_23a7,
+    0xfc93_a039,
+    0x655b_59c3,
+    0x8f0c_cc92,
+    0xffef_f47d,
+    0x8584_5dd1,
+    0x6fa8_7e4f,
+    0xfe2c_e6e0,
+    0xa301_4314,
+    0x4e08_11a1,
+    0xf753_7e82,
+    0xbd3a_f235,
+    0x2ad7_d2bb,
+    0xeb86_d391,
+];
+
+/// An MD5 hasher.
+///
+/// MD5 is broken and must not be used where collisions matter. It is
+/// included for the fingerprints of legacy systems.
+///
+/// # Examples
+///
+/// ```
+/// use boba::fingerprint::Md5;
+///
+/// let mut hasher = Md5::new();
+/// hasher.update("Pine");
+/// hasher.update("apple");
+/// assert_eq!(hasher.finalize(), Md5::digest("Pineapple"));
+/// ```
+#[cfg(feature = "md5")]
+#[derive(Debug, Clone)]
+pub struct Md5 {
+    state: [u32; 4],
+    blocks: Blocks,
+}
+
+#[cfg(feature = "md5")]
+impl Md5 {
+    /// Create a hasher for an empty message.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: MD5_INITIAL,
+            blocks: Blocks::new(),
+        }
+    }
+
+    /// Hash `data` and return the 16 byte hash.
+    #[must_use]
+    pub fn digest<T: AsRef<[u8]>>(data: T) -> [u8; 16] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Append `data` to the hashed message.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        let state = &mut self.state;
+        self.blocks
+            .update(data.as_ref(), |block| md5_compress(state, block));
+    }
+
+    /// Return the 16 byte hash of the message.
+    #[must_use]
+    pub fn finalize(mut self) -> [u8; 16] {
+        let bit_len = self.blocks.bit_len().to_le_bytes();
+        let state = &mut self.state;
+        self.blocks
+            .finish(bit_len, |block| md5_compress(state, block));
+        let mut out = [0; 16];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(feature = "md5")]
+impl Default for Md5 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The names follow the specification.
+#[cfg(feature = "md5")]
+#[allow(clippy::many_single_char_names)]
+fn md5_compress(state: &mut [u32; 4], block: &[u8; BLOCK_LEN]) {
+    let m = words::<true>(block);
+    let [mut a, mut b, mut c, mut d] = *state;
+    for (i, k) in MD5_K.into_iter().enumerate() {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let f = f.wrapping_add(a).wrapping_add(k).wrapping_add(m[g]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(f.rotate_left(MD5_SHIFTS[i / 16][i % 4]));
+    }
+    for (word, x) in state.iter_mut().zip([a, b, c, d]) {
+        *word = word.wrapping_add(x);
+    }
+}
+
+#[cfg(feature = "sha1")]
+const SHA1_INITIAL: [u32; 5] = [
+    0x6745_2301,
+    0xefcd_ab89,
+    0x98ba_dcfe,
+    0x1032_5476,
+    0xc3d2_e1f0,
+];
+
+/// A SHA-1 hasher.
+///
+/// SHA-1 is broken and must not be used where collisions matter. It is
+/// included for the fingerprints printed by `ssh-keygen -B`.
+///
+/// # Examples
+///
+/// ```
+/// use boba::fingerprint::Sha1;
+///
+/// let mut hasher = Sha1::new();
+/// hasher.update("Pine");
+/// hasher.update("apple");
+/// assert_eq!(hasher.finalize(), Sha1::digest("Pineapple"));
+/// ```
+#[cfg(feature = "sha1")]
+#[derive(Debug, Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    blocks: Blocks,
+}
+
+#[cfg(feature = "sha1")]
+impl Sha1 {
+    /// Create a hasher for an empty message.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: SHA1_INITIAL,
+            blocks: Blocks::new(),
+        }
+    }
+
+    /// Hash `data` and return the 20 byte hash.
+    #[must_use]
+    pub fn digest<T: AsRef<[u8]>>(data: T) -> [u8; 20] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Append `data` to the hashed message.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        let state = &mut self.state;
+        self.blocks
+            .update(data.as_ref(), |block| sha1_compress(state, block));
+    }
+
+    /// Return the 20 byte hash of the message.
+    #[must_use]
+    pub fn finalize(mut self) -> [u8; 20] {
+        let bit_len = self.blocks.bit_len().to_be_bytes();
+        let state = &mut self.state;
+        self.blocks
+            .finish(bit_len, |block| sha1_compress(state, block));
+        let mut out = [0; 20];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(feature = "sha1")]
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The names follow the specification.
+#[cfg(feature = "sha1")]
+#[allow(clippy::many_single_char_names)]
+fn sha1_compress(state: &mut [u32; 5], block: &[u8; BLOCK_LEN]) {
+    let mut w = [0; 80];
+    w[..16].copy_from_slice(&words::<false>(block));
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (i, w) in w.into_iter().enumerate() {
+        let (f, k) = match i / 20 {
+            0 => ((b & c) | (!b & d), 0x5a82_7999),
+            1 => (b ^ c ^ d, 0x6ed9_eba1),
+            2 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+            _ => (b ^ c ^ d, 0xca62_c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(w);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+    for (word, x) in state.iter_mut().zip([a, b, c, d, e]) {
+        *word = word.wrapping_add(x);
+    }
+}
+
+#[cfg(feature = "sha256")]
+const SHA256_INITIAL: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+#[cfg(feature = "sha256")]
+const SHA256_K: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+/// A SHA-256 hasher.
+///
+/// # Examples
+///
+/// ```
+/// use boba::fingerprint::Sha256;
+///
+/// let mut hasher = Sha256::new();
+/// hasher.update("Pine");
+/// hasher.update("apple");
+/// assert_eq!(hasher.finalize(), Sha256::digest("Pineapple"));
+/// ```
+#[cfg(feature = "sha256")]
+#[derive(Debug, Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    blocks: Blocks,
+}
+
+#[cfg(feature = "sha256")]
+impl Sha256 {
+    /// Create a hasher for an empty message.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: SHA256_INITIAL,
+            blocks: Blocks::new(),
+        }
+    }
+
+    /// Hash `data` and return the 32 byte hash.
+    #[must_use]
+    pub fn digest<T: AsRef<[u8]>>(data: T) -> [u8; 32] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Append `data` to the hashed message.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        let state = &mut self.state;
+        self.blocks
+            .update(data.as_ref(), |block| sha256_compress(state, block));
+    }
+
+    /// Return the 32 byte hash of the message.
+    #[must_use]
+    pub fn finalize(mut self) -> [u8; 32] {
+        let bit_len = self.blocks.bit_len().to_be_bytes();
+        let state = &mut self.state;
+        self.blocks
+            .finish(bit_len, |block| sha256_compress(state, block));
+        let mut out = [0; 32];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(feature = "sha256")]
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The names follow the specification.
+#[cfg(feature = "sha256")]
+#[allow(clippy::many_single_char_names)]
+fn sha256_compress(state: &mut [u32; 8], block: &[u8; BLOCK_LEN]) {
+    let mut w = [0; 64];
+    w[..16].copy_from_slice(&words::<false>(block));
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for (k, w) in SHA256_K.into_iter().zip(w) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(k)
+            .wrapping_add(w);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    for (word, x) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use core::fmt::Write;
+
+    use super::*;
+    use crate::testing::data;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().fold(String::new(), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+    }
+
+    const ABC_448: &str = "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+
+    #[test]
+    #[cfg(feature = "md5")]
+    fn test_md5_vectors() {
+        let cases = [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (ABC_448, "8215ef0796a20bcaaae116d3876c664a"),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(hex(&Md5::digest(data)), expected, "{data}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sha1")]
+    fn test_sha1_vectors() {
+        let cases = [
+            ("", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            ("abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (ABC_448, "84983e441c3bd26ebaae4aa1f95129e5e54670f1"),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(hex(&Sha1::digest(data)), expected, "{data}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sha256")]
+    fn test_sha256_vectors() {
+        let cases = [
+            (
+                "",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                "abc",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                ABC_448,
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            ),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(hex(&Sha256::digest(data)), expected, "{data}");
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "md5", feature = "sha1", feature = "sha256"))]
+    fn test_million_a() {
+        let mut md5 = Md5::new();
+        let mut sha1 = Sha1::new();
+        let mut sha256 = Sha256::new();
+        for _ in 0..1000 {
+            let chunk = [b'a'; 1000];
+            md5.update(chunk);
+            sha1.update(chunk);
+            sha256.update(chunk);
+        }
+        assert_eq!(hex(&md5.finalize()), "7707d6ae4e027c70eea2a935c2296f21");
+        assert_eq!(
+            hex(&sha1.finalize()),
+            "34aa973cd4c4daa4f61eeb2bdbad27316534016f"
+        );
+        assert_eq!(
+            hex(&sha256.finalize()),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "md5", feature = "sha1", feature = "sha256"))]
+    fn test_matches_rustcrypto() {
+        use sha2::Digest;
+
+        let data = data(4 * BLOCK_LEN + 1);
+        for len in 0..data.len() {
+            let data = &data[..len];
+            assert_eq!(Md5::digest(data), *md5::Md5::digest(data), "{len}");
+            assert_eq!(Sha1::digest(data), *sha1::Sha1::digest(data), "{len}");
+            assert_eq!(Sha256::digest(data), *sha2::Sha256::digest(data), "{len}");
+        }
+    }
+
+    macro_rules! check_update_splits {
+        ($hasher:ident) => {
+            let data = data(3 * BLOCK_LEN + 7);
+            let expected = $hasher::digest(&data);
+            for split in [1, 7, 55, 56, 63, 64, 65, 128] {
+                let mut hasher = $hasher::new();
+                for chunk in data.chunks(split) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hasher.finalize(), expected, "split {split}");
+            }
+        };
+    }
+
+    #[test]
+    fn test_update_splits() {
+        #[cfg(feature = "md5")]
+        check_update_splits!(Md5);
+        #[cfg(feature = "sha1")]
+        check_update_splits!(Sha1);
+        #[cfg(feature = "sha256")]
+        check_update_splits!(Sha256);
+    }
+}
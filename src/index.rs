@@ -0,0 +1,314 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::DecodeError;
+
+/// Length of a word in an encoding, including its trailing `-` separator.
+const WORD_STRIDE: usize = 6;
+
+/// The result of resolving a prefix with [`PrefixIndex::resolve`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// No entry in the index starts with the prefix.
+    NotFound,
+    /// Exactly one entry in the index starts with the prefix.
+    Unique(usize),
+    /// More than one entry in the index starts with the prefix.
+    ///
+    /// The candidates are sorted by their encoding.
+    Ambiguous(Vec<usize>),
+}
+
+/// An index of Bubble Babble encodings that can identify its entries by the
+/// shortest unambiguous prefix of their encoding.
+///
+/// This works like abbreviated commit hashes in git: each entry is assigned an
+/// id on insertion and can be looked up by typing only its first few words.
+///
+/// # Examples
+///
+/// ```
+/// use boba::{PrefixIndex, Resolution};
+///
+/// let mut index = PrefixIndex::new();
+/// let pineapple = index.insert("Pineapple");
+/// let pinecone = index.insert("Pinecone");
+/// let digits = index.insert("1234567890");
+///
+/// assert_eq!(index.get(pineapple), Some("xigak-nyryk-humil-bosek-sonax"));
+/// assert_eq!(index.get(pinecone), Some("xigak-nyryk-humuk-zuryk-huxax"));
+///
+/// // The digits are unique after their first word, but the two pines share
+/// // their first two words.
+/// assert_eq!(index.shortest_prefix(digits), Some("xesef"));
+/// assert_eq!(index.shortest_prefix(pineapple), Some("xigak-nyryk-humil"));
+///
+/// assert_eq!(index.resolve("xigak-nyryk-humi"), Resolution::Unique(pineapple));
+/// assert_eq!(
+///     index.resolve("xigak"),
+///     Resolution::Ambiguous(vec![pineapple, pinecone])
+/// );
+/// assert_eq!(index.resolve("xuzob"), Resolution::NotFound);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PrefixIndex {
+    entries: Vec<String>,
+    // Entry ids ordered by their encoding.
+    sorted: Vec<usize>,
+}
+
+impl PrefixIndex {
+    /// Create a new, empty index.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            sorted: Vec::new(),
+        }
+    }
+
+    /// Return the number of entries in the index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return whether the index has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Encode the given data and add it to the index.
+    ///
+    /// Returns the id of the entry. If the data is already in the index, the id
+    /// of the existing entry is returned.
+    pub fn insert<T: AsRef<[u8]>>(&mut self, data: T) -> usize {
+        let encoded = crate::encode::inner(data.as_ref());
+        match self.search(&encoded) {
+            Ok(pos) => self.sorted[pos],
+            Err(pos) => {
+                let id = self.entries.len();
+                self.entries.push(encoded);
+                self.sorted.insert(pos, id);
+                id
+            }
+        }
+    }
+
+    /// Add an existing Bubble Babble encoding to the index.
+    ///
+    /// Returns the id of the entry. If the encoded data is already in the
+    /// index, the id of the existing entry is returned.
+    ///
+    /// # Errors
+    ///
+    /// If `encoded` is not a valid Bubble Babble encoding, a [`DecodeError`] is
+    /// returned.
+    pub fn insert_encoded<T: AsRef<[u8]>>(&mut self, encoded: T) -> Result<usize, DecodeError> {
+        let data = crate::decode::inner(encoded.as_ref())?;
+        Ok(self.insert(data))
+    }
+
+    /// Return the encoding of the entry with the given id.
+    #[must_use]
+    pub fn get(&self, id: usize) -> Option<&str> {
+        self.entries.get(id).map(String::as_str)
+    }
+
+    /// Return the minimum number of leading words needed to distinguish the
+    /// entry with the given id from every other entry in the index.
+    ///
+    /// An entry that is the only one in the index needs one word.
+    #[must_use]
+    pub fn unique_words(&self, id: usize) -> Option<usize> {
+        let encoded = self.entries.get(id)?;
+        let Ok(pos) = self.search(encoded) else {
+            return None;
+        };
+        let neighbors = [
+            pos.checked_sub(1).and_then(|prev| self.sorted.get(prev)),
+            self.sorted.get(pos + 1),
+        ];
+        let common = neighbors
+            .into_iter()
+            .flatten()
+            .map(|&other| common_prefix_len(encoded, &self.entries[other]))
+            .max()
+            .unwrap_or(0);
+        // The first differing character must be visible. A prefix of `n` words
+        // is `6 * n - 1` characters long because the trailing separator is
+        // omitted.
+        let visible = common + 1;
+        let words = (visible + 1).div_ceil(WORD_STRIDE);
+        Some(words.max(1))
+    }
+
+    /// Return the shortest prefix of whole words that distinguishes the entry
+    /// with the given id from every other entry in the index.
+    #[must_use]
+    pub fn shortest_prefix(&self, id: usize) -> Option<&str> {
+        let words = self.unique_words(id)?;
+        let encoded = self.entries.get(id)?;
+        let len = (words * WORD_STRIDE - 1).min(encoded.len());
+        encoded.get(..len)
+    }
+
+    /// Resolve a prefix of an encoding to the entries that start with it.
+    ///
+    /// The prefix may end in the middle of a word. Leading and trailing
+    /// whitespace is ignored and the prefix is matched case-insensitively.
+    #[must_use]
+    pub fn resolve<T: AsRef<str>>(&self, prefix: T) -> Resolution {
+        let prefix = prefix.as_ref().trim().to_ascii_lowercase();
+        let start = self
+            .sorted
+            .partition_point(|&id| self.entries[id].as_str() < prefix.as_str());
+        let mut candidates = self.sorted[start..]
+            .iter()
+            .copied()
+            .take_while(|&id| self.entries[id].starts_with(&prefix))
+            .collect::<Vec<_>>();
+        match candidates.len() {
+            0 => Resolution::NotFound,
+            1 => Resolution::Unique(candidates.remove(0)),
+            _ => Resolution::Ambiguous(candidates),
+        }
+    }
+
+    /// Return an iterator over the ids and encodings of the entries in the
+    /// index in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        self.entries.iter().map(String::as_str).enumerate()
+    }
+
+    fn search(&self, encoded: &str) -> Result<usize, usize> {
+        self.sorted
+            .binary_search_by(|&id| self.entries[id].as_str().cmp(encoded))
+    }
+}
+
+impl<T: AsRef<[u8]>> Extend<T> for PrefixIndex {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.insert(data);
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<T> for PrefixIndex {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut index = Self::new();
+        index.extend(iter);
+        index
+    }
+}
+
+fn common_prefix_len(left: &str, right: &str) -> usize {
+    left.bytes()
+        .zip(right.bytes())
+        .take_while(|(l, r)| l == r)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_insert_deduplicates() {
+        let mut index = PrefixIndex::new();
+        assert!(index.is_empty());
+        let first = index.insert("Pineapple");
+        let second = index.insert("Pineapple");
+        assert_eq!(first, second);
+        assert_eq!(index.len(), 1);
+        assert_eq!(
+            index.insert_encoded("xigak-nyryk-humil-bosek-sonax"),
+            Ok(first)
+        );
+        assert_eq!(
+            index.insert_encoded("xigak-nyryk-humil-bosek-sonox"),
+            Err(DecodeError::Corrupted)
+        );
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_unique_words_single_entry() {
+        let index = ["1234567890"].into_iter().collect::<PrefixIndex>();
+        assert_eq!(index.unique_words(0), Some(1));
+        assert_eq!(index.shortest_prefix(0), Some("xesef"));
+        assert_eq!(index.unique_words(1), None);
+        assert_eq!(index.shortest_prefix(1), None);
+    }
+
+    #[test]
+    fn test_unique_words() {
+        // "Pineapple" and "Pinecone" share their first two words and the first
+        // three letters of their third word.
+        let index = ["Pineapple", "Pinecone", "1234567890"]
+            .into_iter()
+            .collect::<PrefixIndex>();
+        assert_eq!(index.unique_words(0), Some(3));
+        assert_eq!(index.unique_words(1), Some(3));
+        assert_eq!(index.unique_words(2), Some(1));
+        assert_eq!(index.shortest_prefix(1), Some("xigak-nyryk-humuk"));
+    }
+
+    #[test]
+    fn test_unique_words_differ_at_separator() {
+        // "Pineapple" and "Pineapples" share every letter of "Pineapple"
+        // except the trailer, which becomes a `-` in the longer encoding.
+        let index = ["Pineapple", "Pineapples"]
+            .into_iter()
+            .collect::<PrefixIndex>();
+        assert_eq!(index.unique_words(0), Some(5));
+        assert_eq!(index.unique_words(1), Some(5));
+        assert_eq!(
+            index.shortest_prefix(0),
+            Some("xigak-nyryk-humil-bosek-sonax")
+        );
+        assert_eq!(
+            index.shortest_prefix(1),
+            Some("xigak-nyryk-humil-bosek-sonal")
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let index = ["Pineapple", "Pinecone", "1234567890"]
+            .into_iter()
+            .collect::<PrefixIndex>();
+        assert_eq!(index.resolve("xesef"), Resolution::Unique(2));
+        assert_eq!(index.resolve("xe"), Resolution::Unique(2));
+        assert_eq!(index.resolve("  XIGAK-NYRYK-HUMIL "), Resolution::Unique(0));
+        assert_eq!(
+            index.resolve("xigak-nyryk-hum"),
+            Resolution::Ambiguous(vec![0, 1])
+        );
+        assert_eq!(index.resolve(""), Resolution::Ambiguous(vec![2, 0, 1]));
+        assert_eq!(
+            index.resolve("xigak-nyryk-humil-bosek-sonax-"),
+            Resolution::NotFound
+        );
+        assert_eq!(index.resolve("xz"), Resolution::NotFound);
+    }
+
+    #[test]
+    fn test_iter_is_in_insertion_order() {
+        let index = ["Pineapple", "1234567890"]
+            .into_iter()
+            .collect::<PrefixIndex>();
+        let entries = index.iter().collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            [
+                (0, "xigak-nyryk-humil-bosek-sonax"),
+                (1, "xesef-disof-gytuf-katof-movif-baxux"),
+            ]
+        );
+    }
+}
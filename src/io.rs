@@ -0,0 +1,432 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use std::io::{self, Read, Write};
+
+use crate::decode::{decode_tail, decode_words, find_invalid_byte};
+use crate::encode::{tail, write_pairs};
+use crate::{Checksum, DecodeError};
+
+/// The number of words encoded or decoded at a time.
+const CHUNK_WORDS: usize = 1024;
+
+/// A writer that encodes the data written to it with the Bubble Babble
+/// encoding and writes the encoding to an inner writer.
+///
+/// The header is written before the first word, and the final 3-tuple and
+/// trailer are written by [`finish`](Self::finish). An encoding is incomplete
+/// until `finish` is called. Dropping the writer does not finish it.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use boba::EncodeWriter;
+///
+/// # fn example() -> std::io::Result<()> {
+/// let mut writer = EncodeWriter::new(Vec::new());
+/// writer.write_all(b"Pine")?;
+/// writer.write_all(b"apple")?;
+/// let encoded = writer.finish()?;
+/// assert_eq!(encoded, b"xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct EncodeWriter<W: Write> {
+    inner: W,
+    checksum: Checksum,
+    // The last byte of odd length data written so far, which is not yet part
+    // of a word.
+    pending: Option<u8>,
+    started: bool,
+}
+
+impl<W: Write> EncodeWriter<W> {
+    /// Create a writer that writes the encoding of its input to `inner`.
+    pub const fn new(inner: W) -> Self {
+        Self {
+            inner,
+            checksum: Checksum::initial(),
+            pending: None,
+            started: false,
+        }
+    }
+
+    /// Write the end of the encoding and return the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the inner writer fails, the error is returned.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_header()?;
+        self.inner.write_all(&tail(self.pending, self.checksum))?;
+        self.inner.write_all(b"x")?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Return a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if !self.started {
+            self.inner.write_all(b"x")?;
+            self.started = true;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some((&first, rest)) = buf.split_first() else {
+            return Ok(0);
+        };
+        self.write_header()?;
+        let mut words = [0; 6 * CHUNK_WORDS];
+        let mut data = buf;
+        if let Some(left) = self.pending.take() {
+            let word = &mut words[..6];
+            self.checksum = write_pairs(&[left, first], self.checksum, word);
+            self.inner.write_all(word)?;
+            data = rest;
+        }
+        let (pairs, remainder) = data.split_at(data.len() & !1);
+        for chunk in pairs.chunks(2 * CHUNK_WORDS) {
+            let words = &mut words[..3 * chunk.len()];
+            self.checksum = write_pairs(chunk, self.checksum, words);
+            self.inner.write_all(words)?;
+        }
+        self.pending = remainder.first().copied();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Header,
+    Words,
+    Done,
+    Failed(DecodeError),
+}
+
+/// A reader that decodes a Bubble Babble encoding read from an inner reader.
+///
+/// The encoding is decoded in chunks as it is read, so it need not fit in
+/// memory. Invalid input is reported with an error of kind [`InvalidData`]
+/// wrapping a [`DecodeError`] once the reader reaches it. Data before the
+/// invalid input has already been returned by then and must not be trusted
+/// until the reader returns end of file, when the checksum has been verified.
+///
+/// Errors are reported in the order they are found in the stream. For input
+/// with more than one problem, the error may differ from the one reported by
+/// [`decode`](crate::decode()), which looks at the whole input first.
+///
+/// [`InvalidData`]: io::ErrorKind::InvalidData
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use boba::DecodeReader;
+///
+/// # fn example() -> std::io::Result<()> {
+/// let mut reader = DecodeReader::new(&b"xigak-nyryk-humil-bosek-sonax"[..]);
+/// let mut decoded = Vec::new();
+/// reader.read_to_end(&mut decoded)?;
+/// assert_eq!(decoded, b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct DecodeReader<R: Read> {
+    inner: R,
+    state: State,
+    checksum: Checksum,
+    // Encoded bytes read from `inner` that are not yet decoded.
+    encoded: Vec<u8>,
+    start: usize,
+    end: usize,
+    // The position in the encoding of `encoded[start]`.
+    offset: usize,
+    eof: bool,
+    // Decoded bytes that are not yet returned.
+    decoded: Vec<u8>,
+    decoded_start: usize,
+    decoded_end: usize,
+}
+
+impl<R: Read> DecodeReader<R> {
+    /// Create a reader that decodes the encoding read from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: State::Header,
+            checksum: Checksum::initial(),
+            // Keep enough bytes after the words to find the final 3-tuple and
+            // trailer.
+            encoded: vec![0; 6 * CHUNK_WORDS + 4],
+            start: 0,
+            end: 0,
+            offset: 0,
+            eof: false,
+            decoded: vec![0; 2 * CHUNK_WORDS + 1],
+            decoded_start: 0,
+            decoded_end: 0,
+        }
+    }
+
+    /// Return a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consume the reader and return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Read from the inner reader until the buffer is full or the inner
+    /// reader is exhausted.
+    fn fill(&mut self) -> io::Result<()> {
+        self.encoded.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+        while !self.eof && self.end < self.encoded.len() {
+            match self.inner.read(&mut self.encoded[self.end..]) {
+                Ok(0) => self.eof = true,
+                Ok(read) => self.end += read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode the next chunk of words into `decoded`.
+    fn decode_chunk(&mut self) -> Result<(), DecodeError> {
+        if self.state == State::Header {
+            match self.encoded[self.start..self.end].first() {
+                None => return Err(DecodeError::Corrupted),
+                Some(b'x') => {}
+                Some(_) => return Err(DecodeError::MalformedHeader),
+            }
+            self.start += 1;
+            self.offset += 1;
+            self.state = State::Words;
+        }
+        let available = &self.encoded[self.start..self.end];
+        let words = if self.eof {
+            let [body @ .., b'x'] = available else {
+                return Err(DecodeError::MalformedTrailer);
+            };
+            if let Some(pos) = find_invalid_byte(body, 0) {
+                return Err(DecodeError::InvalidByte(self.offset + pos - 1));
+            }
+            if body.len() % 6 != 3 {
+                return Err(DecodeError::Corrupted);
+            }
+            body.len() / 6
+        } else {
+            available.len().saturating_sub(4) / 6
+        };
+
+        let (pairs, checksum) = decode_words(
+            &available[..6 * words],
+            self.checksum,
+            &mut self.decoded,
+        )
+        .map_err(|err| match err {
+            DecodeError::InvalidByte(pos) => DecodeError::InvalidByte(self.offset + pos - 1),
+            err => err,
+        })?;
+        if pairs < words {
+            // A word is missing its separator.
+            return Err(DecodeError::Corrupted);
+        }
+        self.checksum = checksum;
+        self.start += 6 * words;
+        self.offset += 6 * words;
+        self.decoded_start = 0;
+        self.decoded_end = 2 * words;
+
+        if self.eof && self.end - self.start == 4 {
+            let [left, mid, right, _] = self.encoded[self.start..self.end] else {
+                unreachable!("the final 3-tuple and trailer are 4 bytes");
+            };
+            if let Some(byte) = decode_tail([left, mid, right], checksum)? {
+                self.decoded[self.decoded_end] = byte;
+                self.decoded_end += 1;
+            }
+            self.start = self.end;
+            self.state = State::Done;
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.decoded_start < self.decoded_end || buf.is_empty() {
+                let decoded = &self.decoded[self.decoded_start..self.decoded_end];
+                let len = decoded.len().min(buf.len());
+                buf[..len].copy_from_slice(&decoded[..len]);
+                self.decoded_start += len;
+                return Ok(len);
+            }
+            match self.state {
+                State::Done => return Ok(0),
+                State::Failed(err) => return Err(err.into()),
+                State::Header | State::Words => {}
+            }
+            self.fill()?;
+            if let Err(err) = self.decode_chunk() {
+                self.state = State::Failed(err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use std::io::{self, Read, Write};
+
+    use super::*;
+    use crate::testing::data;
+
+    /// A reader that returns at most `step` bytes per read.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.step.min(buf.len()).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    fn decode_stream(encoded: &[u8], step: usize) -> Result<Vec<u8>, DecodeError> {
+        let mut reader = DecodeReader::new(Trickle {
+            data: encoded,
+            step,
+        });
+        let mut decoded = Vec::new();
+        match reader.read_to_end(&mut decoded) {
+            Ok(_) => Ok(decoded),
+            Err(err) => {
+                assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+                let err = err
+                    .get_ref()
+                    .and_then(|err| err.downcast_ref::<DecodeError>());
+                Err(*err.unwrap())
+            }
+        }
+    }
+
+    #[test]
+    fn test_writer_matches_encode() {
+        let data = data(4 * CHUNK_WORDS + 1);
+        for len in [0, 1, 2, 9, 2 * CHUNK_WORDS, 2 * CHUNK_WORDS + 1, data.len()] {
+            let data = &data[..len];
+            for step in [1, 2, 3, 1000, usize::MAX] {
+                let mut writer = EncodeWriter::new(Vec::new());
+                for chunk in data.chunks(step.min(data.len().max(1))) {
+                    writer.write_all(chunk).unwrap();
+                }
+                let encoded = writer.finish().unwrap();
+                assert_eq!(encoded, crate::encode(data).into_bytes(), "{len} by {step}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_reader_matches_decode() {
+        let data = data(4 * CHUNK_WORDS + 1);
+        for len in [0, 1, 2, 9, 2 * CHUNK_WORDS, 2 * CHUNK_WORDS + 1, data.len()] {
+            let data = &data[..len];
+            let encoded = crate::encode(data);
+            for step in [1, 5, 6, 7, 1000, usize::MAX] {
+                let decoded = decode_stream(encoded.as_bytes(), step);
+                assert_eq!(decoded.as_deref(), Ok(data), "{len} by {step}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_reader_small_reads() {
+        let data = data(101);
+        let encoded = crate::encode(&data);
+        let mut reader = DecodeReader::new(encoded.as_bytes());
+        let mut decoded = Vec::new();
+        let mut buf = [0; 3];
+        loop {
+            match reader.read(&mut buf).unwrap() {
+                0 => break,
+                len => decoded.extend_from_slice(&buf[..len]),
+            }
+        }
+        assert_eq!(decoded, data);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reader_errors() {
+        let cases: [&[u8]; 8] = [
+            b"",
+            b"x",
+            b"yigak-nyryk-humil-bosek-sonax",
+            b"xigak-nyryk-humil-bosek-sonay",
+            b"xigak-nyryk-hu!il-bosek-sonax",
+            b"xigak-nyryk-humil-bosek-sonox",
+            b"xigak-nyryk-humil-bosek-soax",
+            b"xsgak-nyryk-humil-bosek-sonax",
+        ];
+        for encoded in cases {
+            let expected = crate::decode(encoded).unwrap_err();
+            for step in [1, usize::MAX] {
+                assert_eq!(decode_stream(encoded, step), Err(expected), "{encoded:?}");
+            }
+        }
+
+        // Unlike `decode`, the reader does not skip words that are missing
+        // their separator.
+        let encoded = b"xesefxdisofxgytufxkatofxmovifxbaxux";
+        assert_eq!(decode_stream(encoded, 1), Err(DecodeError::Corrupted));
+    }
+
+    #[test]
+    fn test_reader_error_in_later_chunk() {
+        let data = data(8 * CHUNK_WORDS);
+        let mut encoded = crate::encode(&data).into_bytes();
+        // Corrupt a byte in word 2 * CHUNK_WORDS + 1.
+        let pos = 6 * (2 * CHUNK_WORDS + 1) + 3;
+        encoded[pos] = b'!';
+        let mut reader = DecodeReader::new(encoded.as_slice());
+        let mut decoded = Vec::new();
+        let err = reader.read_to_end(&mut decoded).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        // Data before the chunk with the error has been returned.
+        assert!(data.starts_with(&decoded));
+        assert!(decoded.len() > 2 * CHUNK_WORDS && decoded.len() <= 2 * (2 * CHUNK_WORDS + 1));
+        assert_eq!(decode_stream(&encoded, 100), crate::decode(&encoded));
+        // The error is sticky.
+        assert_eq!(
+            reader.read(&mut [0; 8]).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+}
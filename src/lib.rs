@@ -35,22 +35,26 @@
 //!
 //! # Usage
 //!
-//! You can encode binary data by calling [`encode`](encode()):
+//! You can encode binary data by calling [`encode`]:
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! let encoded = boba::encode("Pineapple");
 //! assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+//! # }
 //! ```
 //!
-//! Decoding binary data is done by calling [`decode`](decode()):
+//! Decoding binary data is done by calling [`decode`]:
 //!
 //! ```
 //! # use boba::DecodeError;
+//! # #[cfg(feature = "alloc")]
 //! # fn example() -> Result<(), DecodeError> {
 //! let decoded = boba::decode("xexax")?;
 //! assert_eq!(decoded, vec![]);
 //! # Ok(())
 //! # }
+//! # #[cfg(feature = "alloc")]
 //! # example().unwrap();
 //! ```
 //!
@@ -60,29 +64,336 @@
 //!
 //! ```
 //! # use boba::DecodeError;
+//! # #[cfg(feature = "alloc")] {
 //! let decoded = boba::decode("x🦀x");
 //! // The `DecodeError` contains the offset of the first invalid byte.
 //! assert_eq!(decoded, Err(DecodeError::InvalidByte(1)));
+//! # }
+//! ```
+//!
+//! Without an allocator, encode into and decode from byte slices with
+//! [`encode_slice`] and [`decode_slice`], or format an encoding with
+//! [`display`](display()):
+//!
+//! ```
+//! # use boba::DecodeSliceError;
+//! # fn example() -> Result<(), DecodeSliceError> {
+//! let mut decoded = [0; 16];
+//! let len = boba::decode_slice("xigak-nyryk-humil-bosek-sonax", &mut decoded)?;
+//! assert_eq!(&decoded[..len], b"Pineapple");
+//! # Ok(())
+//! # }
+//! # example().unwrap();
 //! ```
 //!
 //! # Crate Features
 //!
-//! Boba is `no_std` compatible with a required dependency on the [`alloc`]
-//! crate.
-
+//! Boba is `no_std` compatible. The slice and formatting based APIs, like
+//! [`encode_slice`], [`decode_slice`] and [`display`](display()), are always
+//! available and do not allocate. The remaining APIs are enabled by Cargo
+//! features in tiers:
+//!
+//! - **alloc** - Adds the [`String`] and [`Vec`] based APIs, like
+//!   [`encode`] and [`decode`], with a dependency on the
+//!   [`alloc`] crate.
+//! - **std** - Enabled by default. Adds [`EncodeWriter`] and [`DecodeReader`]
+//!   for streaming encoding and decoding through [`std::io`], and
+//!   [`SeekableDecoder`] for random access to the data in encodings stored in
+//!   files and other [`Read`] + [`Seek`] sources. Adds a conversion from
+//!   [`DecodeError`] to [`std::io::Error`]. Implies **alloc**.
+//!
+//! Boba has other Cargo features, all of which are disabled by default:
+//!
+//! - **rayon** - Adds [`par_encode`] and [`par_decode`], which use [`rayon`]
+//!   to encode and decode large inputs on all cores. Implies **alloc**.
+//! - **heapless** - Adds [`encode_heapless`] and [`decode_heapless`], which
+//!   encode to and decode from fixed capacity [`heapless`] collections without
+//!   an allocator.
+//! - **arrayvec** - Adds [`encode_arrayvec`] and [`decode_arrayvec`], which
+//!   encode to and decode from fixed capacity [`arrayvec`] collections without
+//!   an allocator.
+//! - **embedded-io** - Adds [`EmbeddedEncodeWriter`] and
+//!   [`EmbeddedDecodeReader`] for streaming encoding and decoding through
+//!   [`embedded-io`] readers and writers without an allocator.
+//! - **embedded-io-async** - Implements the [`embedded-io-async`] traits for
+//!   the **embedded-io** adapters. Implies **embedded-io**.
+//! - **defmt** - Implements [`defmt::Format`] for [`BubbleBabbleDisplay`] and
+//!   the error types, so firmware can log encodings with [`defmt`].
+//! - **serde** - Adds the [`boba::serde`] modules for serializing bytes as
+//!   Bubble Babble encodings with [`serde`](https://docs.rs/serde). Implies
+//!   **alloc**.
+//! - **clap** - Adds [`BubbleBabbleValueParser`], a [`clap`] value parser for
+//!   Bubble Babble-encoded command line arguments. Implies **std**.
+//! - **bytes** - Adds [`encode_buf`] and [`decode_into_buf`] for encoding
+//!   from and decoding to [`bytes`] buffers. Implies **alloc**.
+//! - **tokio-util** - Adds [`BubbleBabbleCodec`], a [`tokio-util`] codec for
+//!   newline-delimited Bubble Babble frames. Implies **std** and **bytes**.
+//! - **digest** - Adds [`DigestExt`] and [`BubbleBabbleDigest`], which return
+//!   the hashes of [`digest`] hashers, like the [RustCrypto] hashes, in the
+//!   Bubble Babble encoding. Implies **alloc**.
+//! - **md5**, **sha1** and **sha256** - Add self-contained implementations of
+//!   these hashes to the [`fingerprint`] module, along with functions that
+//!   return Bubble Babble fingerprints like `ssh-keygen -B` when **alloc** is
+//!   also enabled.
+//! - **ssh** - Adds the [`ssh`] module for parsing OpenSSH public keys and
+//!   computing and verifying their `ssh-keygen -B` fingerprints. Implies
+//!   **alloc** and **sha1**.
+//! - **x509** - Adds the [`x509`] module for computing the fingerprints of
+//!   PEM and DER X.509 certificates and their `SubjectPublicKeyInfo`. Implies
+//!   **alloc** and **sha256**.
+//!
+//! [`String`]: https://doc.rust-lang.org/alloc/string/struct.String.html
+//! [`Vec`]: https://doc.rust-lang.org/alloc/vec/struct.Vec.html
+//! [`alloc`]: https://doc.rust-lang.org/alloc/index.html
+//! [`std::io`]: https://doc.rust-lang.org/std/io/index.html
+//! [`rayon`]: https://docs.rs/rayon
+//! [`heapless`]: https://docs.rs/heapless
+//! [`arrayvec`]: https://docs.rs/arrayvec
+//! [`embedded-io`]: https://docs.rs/embedded-io
+//! [`embedded-io-async`]: https://docs.rs/embedded-io-async
+//! [`defmt`]: https://docs.rs/defmt
+//! [`clap`]: https://docs.rs/clap
+//! [`bytes`]: https://docs.rs/bytes
+//! [`tokio-util`]: https://docs.rs/tokio-util
+//! [`digest`]: https://docs.rs/digest
+//! [RustCrypto]: https://github.com/RustCrypto/hashes
+//! [`defmt::Format`]: https://docs.rs/defmt/latest/defmt/trait.Format.html
+//! [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+//! [`Seek`]: https://doc.rust-lang.org/std/io/trait.Seek.html
+//! [`std::io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+//
+// Link to items behind disabled features on docs.rs.
+#![cfg_attr(feature = "alloc", doc = "[`encode`]: encode()")]
+#![cfg_attr(feature = "alloc", doc = "[`decode`]: decode()")]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`encode`]: https://docs.rs/boba/latest/boba/fn.encode.html"
+)]
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`decode`]: https://docs.rs/boba/latest/boba/fn.decode.html"
+)]
+#![cfg_attr(
+    not(feature = "std"),
+    doc = "[`EncodeWriter`]: https://docs.rs/boba/latest/boba/struct.EncodeWriter.html"
+)]
+#![cfg_attr(
+    not(feature = "std"),
+    doc = "[`DecodeReader`]: https://docs.rs/boba/latest/boba/struct.DecodeReader.html"
+)]
+#![cfg_attr(
+    not(feature = "std"),
+    doc = "[`SeekableDecoder`]: https://docs.rs/boba/latest/boba/struct.SeekableDecoder.html"
+)]
+#![cfg_attr(
+    not(feature = "rayon"),
+    doc = "[`par_encode`]: https://docs.rs/boba/latest/boba/fn.par_encode.html"
+)]
+#![cfg_attr(
+    not(feature = "rayon"),
+    doc = "[`par_decode`]: https://docs.rs/boba/latest/boba/fn.par_decode.html"
+)]
+#![cfg_attr(
+    not(feature = "heapless"),
+    doc = "[`encode_heapless`]: https://docs.rs/boba/latest/boba/fn.encode_heapless.html"
+)]
+#![cfg_attr(
+    not(feature = "heapless"),
+    doc = "[`decode_heapless`]: https://docs.rs/boba/latest/boba/fn.decode_heapless.html"
+)]
+#![cfg_attr(
+    not(feature = "arrayvec"),
+    doc = "[`encode_arrayvec`]: https://docs.rs/boba/latest/boba/fn.encode_arrayvec.html"
+)]
+#![cfg_attr(
+    not(feature = "arrayvec"),
+    doc = "[`decode_arrayvec`]: https://docs.rs/boba/latest/boba/fn.decode_arrayvec.html"
+)]
+#![cfg_attr(
+    not(feature = "embedded-io"),
+    doc = "[`EmbeddedEncodeWriter`]: https://docs.rs/boba/latest/boba/struct.EmbeddedEncodeWriter.html"
+)]
+#![cfg_attr(
+    not(feature = "embedded-io"),
+    doc = "[`EmbeddedDecodeReader`]: https://docs.rs/boba/latest/boba/struct.EmbeddedDecodeReader.html"
+)]
+#![cfg_attr(feature = "serde", doc = "[`boba::serde`]: crate::serde")]
+#![cfg_attr(
+    not(feature = "serde"),
+    doc = "[`boba::serde`]: https://docs.rs/boba/latest/boba/serde/index.html"
+)]
+#![cfg_attr(
+    not(feature = "clap"),
+    doc = "[`BubbleBabbleValueParser`]: https://docs.rs/boba/latest/boba/struct.BubbleBabbleValueParser.html"
+)]
+#![cfg_attr(
+    not(feature = "bytes"),
+    doc = "[`encode_buf`]: https://docs.rs/boba/latest/boba/fn.encode_buf.html"
+)]
+#![cfg_attr(
+    not(feature = "bytes"),
+    doc = "[`decode_into_buf`]: https://docs.rs/boba/latest/boba/fn.decode_into_buf.html"
+)]
+#![cfg_attr(
+    not(feature = "tokio-util"),
+    doc = "[`BubbleBabbleCodec`]: https://docs.rs/boba/latest/boba/struct.BubbleBabbleCodec.html"
+)]
+#![cfg_attr(
+    not(feature = "digest"),
+    doc = "[`DigestExt`]: https://docs.rs/boba/latest/boba/trait.DigestExt.html"
+)]
+#![cfg_attr(
+    not(feature = "digest"),
+    doc = "[`BubbleBabbleDigest`]: https://docs.rs/boba/latest/boba/struct.BubbleBabbleDigest.html"
+)]
+#![cfg_attr(
+    any(feature = "md5", feature = "sha1", feature = "sha256"),
+    doc = "[`fingerprint`]: crate::fingerprint"
+)]
+#![cfg_attr(
+    not(any(feature = "md5", feature = "sha1", feature = "sha256")),
+    doc = "[`fingerprint`]: https://docs.rs/boba/latest/boba/fingerprint/index.html"
+)]
+#![cfg_attr(feature = "ssh", doc = "[`ssh`]: crate::ssh")]
+#![cfg_attr(
+    not(feature = "ssh"),
+    doc = "[`ssh`]: https://docs.rs/boba/latest/boba/ssh/index.html"
+)]
+#![cfg_attr(feature = "x509", doc = "[`x509`]: crate::x509")]
+#![cfg_attr(
+    not(feature = "x509"),
+    doc = "[`x509`]: https://docs.rs/boba/latest/boba/x509/index.html"
+)]
 #![no_std]
 #![doc(html_root_url = "https://docs.rs/boba/6.0.0")]
 
+// Unit tests use `alloc` regardless of the enabled features.
+#[cfg(any(feature = "alloc", test))]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::fmt;
 
+#[cfg(feature = "alloc")]
+mod abbrev;
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(feature = "ssh"), allow(dead_code))]
+mod base64;
+#[cfg(feature = "alloc")]
+mod batch;
+#[cfg(feature = "bytes")]
+mod buf;
+mod checksum;
+#[cfg(feature = "tokio-util")]
+mod codec;
 mod decode;
+#[cfg(feature = "alloc")]
+mod diff;
+mod display;
+#[cfg(feature = "embedded-io")]
+mod embedded;
 mod encode;
+#[cfg(feature = "alloc")]
+mod encoder;
+#[cfg(any(feature = "md5", feature = "sha1", feature = "sha256"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "md5", feature = "sha1", feature = "sha256")))
+)]
+pub mod fingerprint;
+#[cfg(any(feature = "heapless", feature = "arrayvec"))]
+mod fixed;
+#[cfg(feature = "digest")]
+mod hasher;
+#[cfg(any(feature = "md5", feature = "sha1", feature = "sha256"))]
+mod hashes;
+#[cfg(feature = "alloc")]
+mod index;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "rayon")]
+mod par;
+// Only the decoding state machine is used without `alloc`.
+#[cfg(any(feature = "alloc", feature = "embedded-io"))]
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+mod partial;
+#[cfg(feature = "std")]
+mod seek;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
+#[cfg(feature = "ssh")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ssh")))]
+pub mod ssh;
+#[cfg(all(
+    test,
+    any(
+        feature = "alloc",
+        feature = "embedded-io",
+        feature = "md5",
+        feature = "sha1",
+        feature = "sha256"
+    )
+))]
+mod testing;
+#[cfg(feature = "alloc")]
+mod transcode;
+#[cfg(feature = "clap")]
+mod value_parser;
+mod words;
+#[cfg(feature = "x509")]
+#[cfg_attr(docsrs, doc(cfg(feature = "x509")))]
+pub mod x509;
 
-/// Decoding errors from [`boba::decode`](decode()).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use abbrev::{Abbreviation, Elision};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use batch::{DecodedBatch, EncodedBatch};
+pub use checksum::{Checksum, ChecksumMap};
+#[cfg(feature = "tokio-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-util")))]
+pub use codec::{BubbleBabbleCodec, BubbleBabbleCodecError};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use diff::{Diff, WordDiff, WordStatus};
+pub use display::BubbleBabbleDisplay;
+#[cfg(feature = "embedded-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+pub use embedded::{EmbeddedDecodeError, EmbeddedDecodeReader, EmbeddedEncodeWriter};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use encoder::Encoder;
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub use hasher::{BubbleBabbleDigest, DigestExt};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use index::{PrefixIndex, Resolution};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use io::{DecodeReader, EncodeWriter};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use partial::{Allowed, CharClass, PartialDecoder};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use seek::{CheckpointIndex, SeekableDecoder};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use transcode::TranscodeError;
+#[cfg(feature = "clap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+pub use value_parser::BubbleBabbleValueParser;
+pub use words::{Token, Word, Words};
+
+/// Decoding errors from [`boba::decode`][`decode`].
 ///
 /// `decode` will return a `DecodeError` if:
 ///
@@ -97,6 +408,7 @@ mod encode;
 ///
 /// ```
 /// # use boba::DecodeError;
+/// # #[cfg(feature = "alloc")] {
 /// assert_eq!(boba::decode("x💎🦀x"), Err(DecodeError::InvalidByte(1)));
 /// assert_eq!(boba::decode("x789x"), Err(DecodeError::InvalidByte(1)));
 /// assert_eq!(boba::decode("yx"), Err(DecodeError::MalformedHeader));
@@ -104,8 +416,15 @@ mod encode;
 /// assert_eq!(boba::decode(""), Err(DecodeError::Corrupted));
 /// assert_eq!(boba::decode("z"), Err(DecodeError::Corrupted));
 /// assert_eq!(boba::decode("xx"), Err(DecodeError::Corrupted));
+/// # }
 /// ```
+#[cfg_attr(feature = "alloc", doc = "[`decode`]: decode()")]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`decode`]: https://docs.rs/boba/latest/boba/fn.decode.html"
+)]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum DecodeError {
     /// Checksum mismatch when decoding input.
     ChecksumMismatch,
@@ -127,6 +446,16 @@ pub enum DecodeError {
 
 impl core::error::Error for DecodeError {}
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl From<DecodeError> for std::io::Error {
+    /// Convert a decoding error to an I/O error of kind
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData).
+    fn from(err: DecodeError) -> Self {
+        Self::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
 impl fmt::Display for DecodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -144,6 +473,85 @@ impl fmt::Display for DecodeError {
     }
 }
 
+/// Errors from [`boba::encode_slice`](encode_slice()).
+///
+/// # Examples
+///
+/// ```
+/// # use boba::EncodeSliceError;
+/// let mut out = [0; 8];
+/// assert_eq!(boba::encode_slice("Pine", &mut out), Err(EncodeSliceError::OutputTooSmall));
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EncodeSliceError {
+    /// The output slice is shorter than the encoding.
+    ///
+    /// See [`encoded_len`].
+    OutputTooSmall,
+}
+
+impl core::error::Error for EncodeSliceError {}
+
+impl fmt::Display for EncodeSliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutputTooSmall => f.write_str("Output slice is too small for the encoding"),
+        }
+    }
+}
+
+/// Errors from [`boba::decode_slice`](decode_slice()).
+///
+/// # Examples
+///
+/// ```
+/// # use boba::{DecodeError, DecodeSliceError};
+/// let mut out = [0; 8];
+/// assert_eq!(
+///     boba::decode_slice("xigak-nyryk-humil-bosek-sonax", &mut out),
+///     Err(DecodeSliceError::OutputTooSmall)
+/// );
+/// assert_eq!(
+///     boba::decode_slice("xy", &mut out),
+///     Err(DecodeSliceError::Decode(DecodeError::MalformedTrailer))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecodeSliceError {
+    /// The input is not a valid Bubble Babble encoding.
+    Decode(DecodeError),
+    /// The output slice is shorter than the decoded data.
+    ///
+    /// See [`max_decoded_len`].
+    OutputTooSmall,
+}
+
+impl From<DecodeError> for DecodeSliceError {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl core::error::Error for DecodeSliceError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Decode(err) => Some(err),
+            Self::OutputTooSmall => None,
+        }
+    }
+}
+
+impl fmt::Display for DecodeSliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(err) => fmt::Display::fmt(err, f),
+            Self::OutputTooSmall => f.write_str("Output slice is too small for the decoded data"),
+        }
+    }
+}
+
 /// Encode a byte slice with the Bubble Babble encoding to a [`String`].
 ///
 /// # Examples
@@ -153,6 +561,8 @@ impl fmt::Display for DecodeError {
 /// assert_eq!(boba::encode("1234567890"), "xesef-disof-gytuf-katof-movif-baxux");
 /// assert_eq!(boba::encode("Pineapple"), "xigak-nyryk-humil-bosek-sonax");
 /// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 #[must_use]
 pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
     encode::inner(data.as_ref())
@@ -194,11 +604,726 @@ pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
 /// assert_eq!(boba::decode("z"), Err(DecodeError::Corrupted));
 /// assert_eq!(boba::decode("xx"), Err(DecodeError::Corrupted));
 /// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub fn decode<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, DecodeError> {
     decode::inner(encoded.as_ref())
 }
 
-#[cfg(test)]
+/// Return the length of the Bubble Babble encoding of `len` bytes of data.
+///
+/// Returns `None` if the length overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(boba::encoded_len(0), Some(5));
+/// assert_eq!(boba::encoded_len(9), Some(29));
+/// assert_eq!(boba::encoded_len(10), Some(35));
+/// assert_eq!(boba::encoded_len(usize::MAX), None);
+/// ```
+#[must_use]
+pub const fn encoded_len(len: usize) -> Option<usize> {
+    match (len / 2).checked_mul(6) {
+        Some(words) => words.checked_add(5),
+        None => None,
+    }
+}
+
+/// Return an upper bound for the length of the data decoded from a Bubble
+/// Babble encoding of `encoded_len` bytes.
+///
+/// The bound is exact for encodings of odd length data and one byte larger
+/// than needed for encodings of even length data.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(boba::max_decoded_len(5), 1);
+/// assert_eq!(boba::max_decoded_len(29), 9);
+/// assert_eq!(boba::max_decoded_len(35), 11);
+/// ```
+#[must_use]
+pub const fn max_decoded_len(encoded_len: usize) -> usize {
+    decode::max_decoded_len(encoded_len.saturating_sub(2))
+}
+
+/// Encode a byte slice with the Bubble Babble encoding into the front of
+/// `out` and return the length of the encoding.
+///
+/// This does not allocate. The encoding is ASCII and `out` is at least
+/// [`encoded_len`] bytes long.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::EncodeSliceError;
+/// # fn example() -> Result<(), EncodeSliceError> {
+/// let mut out = [0; 64];
+/// let len = boba::encode_slice("Pineapple", &mut out)?;
+/// assert_eq!(&out[..len], b"xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If `out` is shorter than the encoding, [`EncodeSliceError::OutputTooSmall`]
+/// is returned and `out` is not modified.
+pub fn encode_slice<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<usize, EncodeSliceError> {
+    let data = data.as_ref();
+    match encoded_len(data.len()) {
+        Some(len) if len <= out.len() => Ok(encode::encode_into(data, out)),
+        _ => Err(EncodeSliceError::OutputTooSmall),
+    }
+}
+
+/// Decode a Bubble Babble-encoded byte slice into the front of `out` and
+/// return the length of the decoded data.
+///
+/// This does not allocate. An output slice of [`max_decoded_len`] bytes is
+/// always large enough.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeSliceError;
+/// # fn example() -> Result<(), DecodeSliceError> {
+/// let mut out = [0; 16];
+/// let len = boba::decode_slice("xigak-nyryk-humil-bosek-sonax", &mut out)?;
+/// assert_eq!(&out[..len], b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the input is not a valid encoding, [`DecodeSliceError::Decode`] is
+/// returned with the same error as [`decode`]. If `out` is shorter
+/// than the decoded data, [`DecodeSliceError::OutputTooSmall`] is returned.
+/// The contents of `out` are unspecified after an error.
+///
+#[cfg_attr(feature = "alloc", doc = "[`decode`]: decode()")]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`decode`]: https://docs.rs/boba/latest/boba/fn.decode.html"
+)]
+pub fn decode_slice<T: AsRef<[u8]>>(encoded: T, out: &mut [u8]) -> Result<usize, DecodeSliceError> {
+    decode::decode_slice(encoded.as_ref(), out)
+}
+
+/// Format a byte slice with the Bubble Babble encoding.
+///
+/// The returned [`BubbleBabbleDisplay`] implements [`Display`](fmt::Display)
+/// and writes the encoding without allocating.
+///
+/// # Examples
+///
+/// ```
+/// use core::fmt::Write;
+///
+/// let mut out = String::new();
+/// write!(out, "fingerprint: {}", boba::display("Pineapple"))?;
+/// assert_eq!(out, "fingerprint: xigak-nyryk-humil-bosek-sonax");
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+#[must_use]
+pub fn display<T: AsRef<[u8]> + ?Sized>(data: &T) -> BubbleBabbleDisplay<'_> {
+    BubbleBabbleDisplay::new(data.as_ref())
+}
+
+/// Encode many byte slices with the Bubble Babble encoding into one
+/// contiguous buffer.
+///
+/// This avoids allocating a [`String`] per input, which dominates the cost of
+/// encoding many small inputs like identifiers or digests. See
+/// [`EncodedBatch`] for accessing the encodings.
+///
+/// # Examples
+///
+/// ```
+/// let ids: [[u8; 2]; 3] = [[0, 0], [0, 1], [255, 0]];
+/// let batch = boba::encode_batch(&ids);
+///
+/// assert_eq!(batch.len(), 3);
+/// assert_eq!(
+///     batch.iter().collect::<Vec<_>>(),
+///     ["xebab-byxax", "xebab-caxex", "xuzob-bixux"]
+/// );
+/// assert_eq!(batch.as_bytes(), b"xebab-byxaxxebab-caxexxuzob-bixux");
+/// assert_eq!(batch.span(2), Some(22..33));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use]
+pub fn encode_batch<I, T>(inputs: I) -> EncodedBatch
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    inputs.into_iter().collect()
+}
+
+/// Decode many Bubble Babble-encoded byte slices into one contiguous buffer.
+///
+/// Every input gets an entry in the returned [`DecodedBatch`], which records
+/// a [`DecodeError`] for each input that fails to decode. Decoding continues
+/// with the next input after an error.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// let batch = boba::decode_batch(["xebab-byxax", "xebab-caxax", "xuzob-bixux"]);
+///
+/// assert_eq!(batch.len(), 3);
+/// assert_eq!(batch.errors(), [(1, DecodeError::ChecksumMismatch)]);
+/// assert_eq!(
+///     batch.iter().collect::<Vec<_>>(),
+///     [Ok(&[0, 0][..]), Err(DecodeError::ChecksumMismatch), Ok(&[255, 0][..])]
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use]
+pub fn decode_batch<I, T>(inputs: I) -> DecodedBatch
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    inputs.into_iter().collect()
+}
+
+/// Encode a byte slice with the Bubble Babble encoding to a [`String`] using
+/// all cores.
+///
+/// The output is identical to [`encode`](encode()). The input is split into
+/// chunks whose effect on the running checksum is computed in parallel and
+/// combined with a [`ChecksumMap`], after which every chunk is encoded in
+/// parallel. Small inputs are encoded sequentially.
+///
+/// # Examples
+///
+/// ```
+/// let data = vec![0xA5; 1 << 20];
+/// assert_eq!(boba::par_encode(&data), boba::encode(&data));
+/// assert_eq!(boba::par_encode("Pineapple"), "xigak-nyryk-humil-bosek-sonax");
+/// ```
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+#[must_use]
+pub fn par_encode<T: AsRef<[u8]>>(data: T) -> String {
+    par::encode(data.as_ref())
+}
+
+/// Decode Bubble Babble-encoded byte slice to a [`Vec<u8>`](Vec) using all
+/// cores.
+///
+/// The output is identical to [`decode`](decode()). The checksum-independent
+/// consonants are decoded in parallel, leaving only the bits carried by the
+/// vowels for a sequential pass over the running checksum. Small inputs are
+/// decoded sequentially.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// let data = vec![0xA5; 1 << 20];
+/// let encoded = boba::encode(&data);
+/// assert_eq!(boba::par_decode(&encoded), Ok(data));
+/// assert_eq!(boba::par_decode("xigak-nyryk-humil-bosek-sonax"), Ok(b"Pineapple".to_vec()));
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and returns the same [`DecodeError`] as
+/// [`decode`](decode()) for invalid input.
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub fn par_decode<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, DecodeError> {
+    par::decode(encoded.as_ref())
+}
+
+/// Encode a byte slice with the Bubble Babble encoding to a fixed capacity
+/// [`heapless::String`].
+///
+/// The output is identical to [`encode_slice`]. The encoding of `data` is
+/// [`encoded_len`] bytes long.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::EncodeSliceError;
+/// # fn example() -> Result<(), EncodeSliceError> {
+/// let encoded = boba::encode_heapless::<32, _>("Pineapple")?;
+/// assert_eq!(encoded, "xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the encoding is longer than `N` bytes,
+/// [`EncodeSliceError::OutputTooSmall`] is returned.
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub fn encode_heapless<const N: usize, T: AsRef<[u8]>>(
+    data: T,
+) -> Result<heapless::String<N>, EncodeSliceError> {
+    fixed::encode_heapless(data.as_ref())
+}
+
+/// Decode a Bubble Babble-encoded byte slice to a fixed capacity
+/// [`heapless::Vec`].
+///
+/// The output is identical to [`decode_slice`].
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeSliceError;
+/// # fn example() -> Result<(), DecodeSliceError> {
+/// let decoded = boba::decode_heapless::<16, _>("xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(decoded, b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the input is not a valid encoding, [`DecodeSliceError::Decode`] is
+/// returned. If the decoded data is longer than `N` bytes,
+/// [`DecodeSliceError::OutputTooSmall`] is returned.
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub fn decode_heapless<const N: usize, T: AsRef<[u8]>>(
+    encoded: T,
+) -> Result<heapless::Vec<u8, N>, DecodeSliceError> {
+    fixed::decode_heapless(encoded.as_ref())
+}
+
+/// Encode a byte slice with the Bubble Babble encoding to a fixed capacity
+/// [`arrayvec::ArrayString`].
+///
+/// The output is identical to [`encode_slice`]. The encoding of `data` is
+/// [`encoded_len`] bytes long.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::EncodeSliceError;
+/// # fn example() -> Result<(), EncodeSliceError> {
+/// let encoded = boba::encode_arrayvec::<32, _>("Pineapple")?;
+/// assert_eq!(encoded.as_str(), "xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the encoding is longer than `N` bytes,
+/// [`EncodeSliceError::OutputTooSmall`] is returned.
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+pub fn encode_arrayvec<const N: usize, T: AsRef<[u8]>>(
+    data: T,
+) -> Result<arrayvec::ArrayString<N>, EncodeSliceError> {
+    fixed::encode_arrayvec(data.as_ref())
+}
+
+/// Decode a Bubble Babble-encoded byte slice to a fixed capacity
+/// [`arrayvec::ArrayVec`].
+///
+/// The output is identical to [`decode_slice`].
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeSliceError;
+/// # fn example() -> Result<(), DecodeSliceError> {
+/// let decoded = boba::decode_arrayvec::<16, _>("xigak-nyryk-humil-bosek-sonax")?;
+/// assert_eq!(decoded.as_slice(), b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If the input is not a valid encoding, [`DecodeSliceError::Decode`] is
+/// returned. If the decoded data is longer than `N` bytes,
+/// [`DecodeSliceError::OutputTooSmall`] is returned.
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+pub fn decode_arrayvec<const N: usize, T: AsRef<[u8]>>(
+    encoded: T,
+) -> Result<arrayvec::ArrayVec<u8, N>, DecodeSliceError> {
+    fixed::decode_arrayvec(encoded.as_ref())
+}
+
+/// Encode the remaining bytes of a [`Buf`] with the Bubble Babble encoding
+/// to [`Bytes`].
+///
+/// The output is identical to [`encode`](encode()). `data` is consumed chunk
+/// by chunk without copying it into a contiguous buffer first, so it may be a
+/// chain of buffers, and a pair of bytes may straddle two chunks.
+///
+/// [`Buf`]: bytes::Buf
+/// [`Bytes`]: bytes::Bytes
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Buf;
+///
+/// let data = (&b"Pine"[..]).chain(&b"apple"[..]);
+/// assert_eq!(boba::encode_buf(data), "xigak-nyryk-humil-bosek-sonax");
+/// ```
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+#[must_use]
+pub fn encode_buf<B: bytes::Buf>(data: B) -> bytes::Bytes {
+    buf::encode(data)
+}
+
+/// Decode a Bubble Babble-encoded byte slice into a [`BufMut`] and return
+/// the number of decoded bytes.
+///
+/// The output is identical to [`decode`](decode()). The data is decoded in
+/// blocks on the stack and written to `out` as it is decoded, so it is not
+/// collected in an intermediate buffer first.
+///
+/// [`BufMut`]: bytes::BufMut
+///
+/// # Examples
+///
+/// ```
+/// use bytes::BytesMut;
+///
+/// let mut buf = BytesMut::new();
+/// let len = boba::decode_into_buf("xigak-nyryk-humil-bosek-sonax", &mut buf)?;
+/// assert_eq!(len, 9);
+/// assert_eq!(buf, "Pineapple");
+/// # Ok::<(), boba::DecodeError>(())
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and returns the same [`DecodeError`] as
+/// [`decode`](decode()) for invalid input. Bytes decoded before the error
+/// may have been written to `out`.
+///
+/// # Panics
+///
+/// Panics if `out` does not have enough remaining capacity for the decoded
+/// data, like [`BufMut::put_slice`](bytes::BufMut::put_slice).
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub fn decode_into_buf<T: AsRef<[u8]>, B: bytes::BufMut>(
+    encoded: T,
+    out: &mut B,
+) -> Result<usize, DecodeError> {
+    buf::decode_into(encoded.as_ref(), out)
+}
+
+/// Tokenize a Bubble Babble-encoded byte slice into its [`Token`]s.
+///
+/// The returned iterator yields the leading `x` header, each dash-separated
+/// [`Word`], the `-` separators between them, and the trailing `x`. Each word
+/// reports the range of decoded bytes it carries and the running checksum at
+/// that point, which makes it possible to highlight or compare encodings word
+/// by word.
+///
+/// The iterator performs the same validation as [`decode`] and
+/// yields a [`DecodeError`] as soon as the input is found to be invalid.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::{DecodeError, Token};
+/// # fn example() -> Result<(), DecodeError> {
+/// let encoded = "xigak-nyryk-humil-bosek-sonax";
+/// let mut words = vec![];
+/// for token in boba::words(encoded) {
+///     if let Token::Word(word) = token? {
+///         words.push((&encoded[word.span()], word.bytes()));
+///     }
+/// }
+/// assert_eq!(words[0], ("igak", 0..2));
+/// assert_eq!(words[2], ("humil", 3..6));
+/// assert_eq!(words[4], ("sona", 7..9));
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// Invalid input is reported with the same errors as [`decode`], with one
+/// exception: a word that is missing its `-` separator is always an error.
+/// The decoder stops reading words there and decodes the final word of the
+/// input, which may succeed. The iterator then yields the error the decoder
+/// finds in the final word, or [`DecodeError::Corrupted`] where it finds none.
+///
+/// ```
+/// # use boba::DecodeError;
+/// let mut tokens = boba::words("xesef-disof-gytuf-katof-movif-baxax");
+/// assert!(tokens.by_ref().take(11).all(|token| token.is_ok()));
+/// assert_eq!(tokens.next(), Some(Err(DecodeError::ChecksumMismatch)));
+/// assert_eq!(tokens.next(), None);
+///
+/// // `decode` skips the word missing its separator and decodes no data.
+/// let mut tokens = boba::words("xebabxbexax");
+/// assert_eq!(tokens.nth(1), Some(Err(DecodeError::Corrupted)));
+/// ```
+#[cfg_attr(feature = "alloc", doc = "[`decode`]: decode()")]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`decode`]: https://docs.rs/boba/latest/boba/fn.decode.html"
+)]
+pub fn words<T: AsRef<[u8]> + ?Sized>(encoded: &T) -> Words<'_> {
+    Words::new(encoded.as_ref())
+}
+
+/// Compare two Bubble Babble encodings word by word.
+///
+/// Because every word's vowels are mixed with a running checksum, a change to
+/// a single byte alters the vowels of every later word. The returned [`Diff`]
+/// distinguishes words whose data changed ([`WordStatus::DataChanged`]) from
+/// words that only differ downstream of a change because of the checksum
+/// ([`WordStatus::ChecksumChanged`]).
+///
+/// To compare an encoding with raw bytes, see [`diff_data`].
+///
+/// # Examples
+///
+/// ```
+/// # use boba::{DecodeError, WordStatus};
+/// # fn example() -> Result<(), DecodeError> {
+/// let diff = boba::diff(
+///     "xigak-nyryk-humil-bosek-sonax",
+///     "xigak-nyryk-kymil-bisik-sunux",
+/// )?;
+/// let statuses = diff.words().iter().map(|word| word.status()).collect::<Vec<_>>();
+/// assert_eq!(
+///     statuses,
+///     [
+///         WordStatus::Unchanged,
+///         WordStatus::Unchanged,
+///         WordStatus::DataChanged,
+///         WordStatus::ChecksumChanged,
+///         WordStatus::ChecksumChanged,
+///     ]
+/// );
+/// assert_eq!(diff.changed_bytes(), [3..4]);
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If either input is not a valid Bubble Babble encoding, a [`DecodeError`] is
+/// returned.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn diff<T: AsRef<[u8]>, U: AsRef<[u8]>>(left: T, right: U) -> Result<Diff, DecodeError> {
+    diff::inner(left.as_ref(), right.as_ref())
+}
+
+/// Compare a Bubble Babble encoding with the encoding of raw bytes word by
+/// word.
+///
+/// This is equivalent to calling [`diff`](diff()) with `data` encoded by
+/// [`encode`](encode()).
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// let diff = boba::diff_data("xigak-nyryk-humil-bosek-sonax", "Pineapple")?;
+/// assert!(diff.is_identical());
+///
+/// let diff = boba::diff_data("xigak-nyryk-humil-bosek-sonax", "Pinfapple")?;
+/// assert_eq!(diff.first_data_change().map(|word| word.index()), Some(2));
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If `encoded` is not a valid Bubble Babble encoding, a [`DecodeError`] is
+/// returned.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn diff_data<T: AsRef<[u8]>, U: AsRef<[u8]>>(encoded: T, data: U) -> Result<Diff, DecodeError> {
+    let right = encode::inner(data.as_ref());
+    diff::inner(encoded.as_ref(), right.as_bytes())
+}
+
+/// Check whether an abbreviated Bubble Babble encoding is consistent with the
+/// given data.
+///
+/// Abbreviations are rendered by [`Abbreviation`] and elide the middle of an
+/// encoding either with a single ellipsis, e.g. `xigak-nyryk-…-sonax`, or by
+/// masking each hidden word, e.g. `xigak-*****-*****-bosek-sonax`. The
+/// ellipsis is `…` or `...`, and a mask repeats one character outside of the
+/// Bubble Babble alphabet once for every letter of the word it hides.
+///
+/// This function returns `true` if every visible word of `abbrev` matches the
+/// word at the same position of the encoding of `data`. The first and last
+/// words must be visible, and any other segment that is not a word or a
+/// placeholder is a mismatch, so a mistyped word is never accepted.
+///
+/// # Examples
+///
+/// ```
+/// assert!(boba::matches_abbreviated("xigak-nyryk-…-sonax", "Pineapple"));
+/// assert!(boba::matches_abbreviated("xigak-*****-*****-bosek-sonax", "Pineapple"));
+/// assert!(!boba::matches_abbreviated("xigak-nyryk-…-sonax", "Pinfapple"));
+/// assert!(!boba::matches_abbreviated("xigak-*-*-bosek-sonax", "Pineapple"));
+/// assert!(!boba::matches_abbreviated("…-sonax", "Pineapple"));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use]
+pub fn matches_abbreviated<T: AsRef<str>, U: AsRef<[u8]>>(abbrev: T, data: U) -> bool {
+    abbrev::matches(abbrev.as_ref(), data.as_ref())
+}
+
+/// Encode hex-encoded data with the Bubble Babble encoding.
+///
+/// Hex digits may be upper or lower case, and pairs of digits may be
+/// separated by `:`, `-` or whitespace, like the fingerprints printed by
+/// `openssl x509 -fingerprint` and `ssh-keygen -E md5`. The digits may be
+/// preceded by `0x` or by a label ending in `:` or `=`, like `MD5:` or
+/// `SHA256 Fingerprint=`.
+///
+/// The data is encoded as it is parsed, without collecting it in an
+/// intermediate buffer.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::TranscodeError;
+/// # fn example() -> Result<(), TranscodeError> {
+/// assert_eq!(boba::from_hex("50696e656170706c65")?, "xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(
+///     boba::from_hex("MD5:50:69:6E:65:61:70:70:6C:65")?,
+///     "xigak-nyryk-humil-bosek-sonax"
+/// );
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If `hex` has a byte that is not a hex digit or a separator between pairs
+/// of digits, [`TranscodeError::InvalidByte`] is returned with its position
+/// in `hex`. If `hex` has an odd number of digits,
+/// [`TranscodeError::Truncated`] is returned.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<String, TranscodeError> {
+    transcode::from_hex(hex.as_ref())
+}
+
+/// Decode a Bubble Babble-encoded byte slice to lowercase hex.
+///
+/// The data is written as hex as it is decoded, without collecting it in an
+/// intermediate buffer.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// assert_eq!(boba::to_hex("xigak-nyryk-humil-bosek-sonax")?, "50696e656170706c65");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and returns the same [`DecodeError`] as
+/// [`decode`](decode()) for invalid input.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn to_hex<T: AsRef<[u8]>>(encoded: T) -> Result<String, DecodeError> {
+    transcode::to_hex(encoded.as_ref())
+}
+
+/// Encode base64-encoded data with the Bubble Babble encoding.
+///
+/// The input is standard base64 with optional padding, like the `SHA256:`
+/// fingerprints printed by `ssh-keygen -l`, and may be wrapped over several
+/// lines. The base64 may be preceded by a label ending in `:`, like
+/// `SHA256:`.
+///
+/// The data is encoded as it is parsed, without collecting it in an
+/// intermediate buffer.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::TranscodeError;
+/// # fn example() -> Result<(), TranscodeError> {
+/// assert_eq!(boba::from_base64("UGluZWFwcGxl")?, "xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(boba::from_base64("SHA256:UGluZWFwcGxl")?, "xigak-nyryk-humil-bosek-sonax");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// If `base64` has a byte outside of the base64 alphabet or misplaced
+/// padding, [`TranscodeError::InvalidByte`] is returned with its position in
+/// `base64`. If `base64` ends in the middle of a byte,
+/// [`TranscodeError::Truncated`] is returned.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn from_base64<T: AsRef<[u8]>>(base64: T) -> Result<String, TranscodeError> {
+    transcode::from_base64(base64.as_ref())
+}
+
+/// Decode a Bubble Babble-encoded byte slice to standard base64 with
+/// padding.
+///
+/// The data is written as base64 as it is decoded, without collecting it in
+/// an intermediate buffer. OpenSSH prints `SHA256:` fingerprints without the
+/// padding.
+///
+/// # Examples
+///
+/// ```
+/// # use boba::DecodeError;
+/// # fn example() -> Result<(), DecodeError> {
+/// assert_eq!(boba::to_base64("xigak-nyryk-humil-bosek-sonax")?, "UGluZWFwcGxl");
+/// assert_eq!(boba::to_base64("xesef-disof-gytuf-katof-movif-baxux")?, "MTIzNDU2Nzg5MA==");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Decoding is fallible and returns the same [`DecodeError`] as
+/// [`decode`](decode()) for invalid input.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn to_base64<T: AsRef<[u8]>>(encoded: T) -> Result<String, DecodeError> {
+    transcode::to_base64(encoded.as_ref())
+}
+
+#[cfg(all(test, feature = "alloc"))]
 #[allow(clippy::non_ascii_literal)]
 mod tests {
     use alloc::string::String;
@@ -337,6 +1462,6 @@ mod tests {
 //
 // This module and macro declaration should be kept at the end of the file, in
 // order to not interfere with code coverage.
-#[cfg(doctest)]
+#[cfg(all(doctest, feature = "alloc"))]
 #[doc = include_str!("../README.md")]
 mod readme {}
@@ -0,0 +1,145 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use crate::decode::{decode_consonants, decode_tail, decode_vowels, strip_header_trailer};
+use crate::encode::{ascii_to_string, encode_into_with, encoded_len, write_pairs};
+use crate::{Checksum, ChecksumMap, DecodeError};
+
+/// The number of byte pairs processed by each parallel task.
+const CHUNK_PAIRS: usize = 16 * 1024;
+
+/// Inputs with fewer byte pairs than this are not worth splitting up and are
+/// processed sequentially.
+const MIN_PAR_PAIRS: usize = 4 * CHUNK_PAIRS;
+
+pub fn encode(data: &[u8]) -> String {
+    if data.len() / 2 < MIN_PAR_PAIRS {
+        return crate::encode::inner(data);
+    }
+    let mut encoded = vec![0; encoded_len(data.len())];
+    encode_into_with(data, &mut encoded, |pairs, words| {
+        let chunks = pairs.par_chunks(2 * CHUNK_PAIRS);
+        // The checksum is an affine recurrence, so the effect of each chunk
+        // on it can be computed independently. A prefix scan over these maps
+        // yields the checksum state entering every chunk.
+        let maps = chunks
+            .clone()
+            .map(ChecksumMap::from_bytes)
+            .collect::<Vec<_>>();
+        let mut starts = Vec::with_capacity(maps.len());
+        let mut checksum = Checksum::initial();
+        for map in maps {
+            starts.push(checksum);
+            checksum = Checksum::combine(checksum, map);
+        }
+        words
+            .par_chunks_mut(6 * CHUNK_PAIRS)
+            .zip(chunks)
+            .zip(starts)
+            .for_each(|((words, chunk), start)| {
+                write_pairs(chunk, start, words);
+            });
+        checksum
+    });
+    ascii_to_string(encoded)
+}
+
+pub fn decode(encoded: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    if encoded.len() / 6 < MIN_PAR_PAIRS {
+        return crate::decode::inner(encoded);
+    }
+    // Errors are rare. Rather than reproduce the error precedence of the
+    // sequential decoder in parallel, give up on the first anomaly and let
+    // the sequential decoder report it.
+    match try_decode(encoded) {
+        Some(decoded) => Ok(decoded),
+        None => crate::decode::inner(encoded),
+    }
+}
+
+fn try_decode(encoded: &[u8]) -> Option<Vec<u8>> {
+    let enc = strip_header_trailer(encoded).ok()?;
+    let (words, tail) = enc.split_at(enc.len() - enc.len() % 6);
+    let &[left, mid, right] = tail else {
+        return None;
+    };
+    let pairs = words.len() / 6;
+    let mut decoded = vec![0; 2 * pairs + 1];
+    let mut vowels = vec![[0; 2]; pairs];
+
+    // The consonants carry the right byte of every pair and the middle bits
+    // of the left byte. They do not depend on the checksum and are decoded in
+    // parallel.
+    let valid = words
+        .par_chunks(6 * CHUNK_PAIRS)
+        .zip(decoded[..2 * pairs].par_chunks_mut(2 * CHUNK_PAIRS))
+        .zip(vowels.par_chunks_mut(CHUNK_PAIRS))
+        .all(|((words, out), vowels)| decode_consonants(words, out, vowels));
+    if !valid {
+        return None;
+    }
+    // Only the vowel bits need the sequential pass over the running checksum.
+    let checksum = decode_vowels(&mut decoded[..2 * pairs], &vowels, Checksum::initial())?;
+    match decode_tail([left, mid, right], checksum).ok()? {
+        Some(byte) => decoded[2 * pairs] = byte,
+        None => decoded.truncate(2 * pairs),
+    }
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::data;
+
+    #[test]
+    fn test_encode_matches_sequential() {
+        let data = data(2 * MIN_PAR_PAIRS + 3 * CHUNK_PAIRS + 1);
+        for len in [0, 1, 2 * MIN_PAR_PAIRS, 2 * MIN_PAR_PAIRS + 1, data.len()] {
+            let data = &data[..len];
+            assert_eq!(encode(data), crate::encode::inner(data));
+        }
+    }
+
+    #[test]
+    fn test_decode_matches_sequential() {
+        let data = data(2 * MIN_PAR_PAIRS + 3 * CHUNK_PAIRS + 1);
+        for len in [0, 1, 2 * MIN_PAR_PAIRS, 2 * MIN_PAR_PAIRS + 1, data.len()] {
+            let data = &data[..len];
+            let encoded = encode(data);
+            assert_eq!(decode(encoded.as_bytes()).as_deref(), Ok(data));
+        }
+    }
+
+    #[test]
+    fn test_decode_errors_match_sequential() {
+        let encoded = encode(&data(2 * MIN_PAR_PAIRS + 1)).into_bytes();
+        let len = encoded.len();
+        // positions in the first word, in a later chunk, and in the trailer
+        for pos in [1, 3, 5, 6 * CHUNK_PAIRS + 2, len - 4, len - 2, len - 1] {
+            for byte in [b'a', b'b', b'x', b'-', b'Q'] {
+                let mut corrupted = encoded.clone();
+                corrupted[pos] = byte;
+                assert_eq!(
+                    decode(&corrupted),
+                    crate::decode::inner(&corrupted),
+                    "corrupted {pos} with {}",
+                    char::from(byte)
+                );
+            }
+        }
+        assert_eq!(
+            decode(&encoded[..len - 1]),
+            Err(DecodeError::MalformedTrailer)
+        );
+
+        // flip the checksum in the final word of an even length input
+        let mut corrupted = encode(&data(2 * MIN_PAR_PAIRS)).into_bytes();
+        let pos = corrupted.len() - 4;
+        corrupted[pos] = if corrupted[pos] == b'a' { b'e' } else { b'a' };
+        assert_eq!(decode(&corrupted), Err(DecodeError::ChecksumMismatch));
+    }
+}
@@ -0,0 +1,510 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::decode::{decode_3_tuple, index_from_consonant, index_from_vowel, is_alphabet};
+use crate::{Checksum, DecodeError};
+
+// The encoding alphabet in the order used by the `Allowed` bitset.
+const ALPHABET: &[u8; 24] = b"aeiouybcdfghklmnprstvzx-";
+const VOWELS: u32 = 0b11_1111;
+const CONSONANTS: u32 = 0b1111_1111_1111_1111 << 6;
+const X: u32 = 1 << 22;
+const SEPARATOR: u32 = 1 << 23;
+
+/// A class of characters in the Bubble Babble alphabet.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CharClass {
+    /// One of the six vowels `aeiouy`.
+    Vowel,
+    /// One of the sixteen consonants `bcdfghklmnprstvz`.
+    Consonant,
+    /// The `-` separator between words.
+    Separator,
+    /// The letter `x`, which is used for the header, the trailer, and to mark
+    /// the final checksum word.
+    X,
+}
+
+/// The set of characters that may come next in a partial Bubble Babble
+/// encoding.
+///
+/// This set is returned by [`PartialDecoder::allowed`]. Vowels are only
+/// included if they are consistent with the running checksum.
+#[derive(Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Allowed {
+    bits: u32,
+}
+
+impl Allowed {
+    /// Return whether the given byte is allowed.
+    #[must_use]
+    pub fn contains(self, byte: u8) -> bool {
+        ALPHABET
+            .iter()
+            .position(|&b| b == byte)
+            .is_some_and(|pos| self.bits & (1 << pos) != 0)
+    }
+
+    /// Return whether any character of the given class is allowed.
+    #[must_use]
+    pub fn accepts(self, class: CharClass) -> bool {
+        let mask = match class {
+            CharClass::Vowel => VOWELS,
+            CharClass::Consonant => CONSONANTS,
+            CharClass::Separator => SEPARATOR,
+            CharClass::X => X,
+        };
+        self.bits & mask != 0
+    }
+
+    /// Return whether no characters are allowed.
+    ///
+    /// This is only the case once the encoding is complete.
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    /// Return the number of allowed characters.
+    #[must_use]
+    pub fn len(self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Return an iterator over the allowed characters.
+    ///
+    /// Vowels are yielded first, then consonants, `x`, and `-`.
+    pub fn iter(self) -> impl Iterator<Item = u8> {
+        ALPHABET
+            .iter()
+            .enumerate()
+            .filter(move |&(pos, _)| self.bits & (1 << pos) != 0)
+            .map(|(_, &byte)| byte)
+    }
+
+    fn vowels(offset: u8) -> Self {
+        // Data vowels encode two bits shifted by the checksum, so exactly four
+        // of the six vowels are valid at any position.
+        let bits = (0..4).fold(0, |bits, bits_value| {
+            bits | 1 << ((bits_value + offset) % 6)
+        });
+        Self { bits }
+    }
+}
+
+impl fmt::Debug for Allowed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter().map(char::from)).finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Header,
+    Left,
+    Mid { a: u8 },
+    Right { a: u8, b: u8 },
+    ChecksumRight,
+    Up,
+    Separator { up: u8 },
+    Down { up: u8 },
+    Trailer,
+    Complete,
+}
+
+/// An allocation-free state machine that decodes Bubble Babble one byte at a
+/// time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Machine {
+    position: Position,
+    checksum: Checksum,
+    byte1: u8,
+    consumed: usize,
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Machine {
+    pub const fn new() -> Self {
+        Self {
+            position: Position::Header,
+            checksum: Checksum::initial(),
+            byte1: 0,
+            consumed: 0,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self.position, Position::Complete)
+    }
+
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    pub fn allowed(&self) -> Allowed {
+        let checksum = self.checksum.value();
+        match self.position {
+            Position::Header | Position::Trailer => Allowed { bits: X },
+            Position::Left => Allowed::vowels(checksum % 6),
+            Position::Mid { a } if a == checksum % 6 => Allowed {
+                bits: CONSONANTS | X,
+            },
+            Position::Mid { .. } | Position::Down { .. } => Allowed { bits: CONSONANTS },
+            Position::Right { .. } => Allowed::vowels(checksum / 6),
+            Position::ChecksumRight => Allowed {
+                bits: 1 << (checksum / 6),
+            },
+            Position::Up => Allowed {
+                bits: CONSONANTS | X,
+            },
+            Position::Separator { .. } => Allowed { bits: SEPARATOR },
+            Position::Complete => Allowed::default(),
+        }
+    }
+
+    /// Advance the state machine by one byte of encoded input.
+    ///
+    /// Returns a decoded byte if `byte` completed one. If `byte` is rejected,
+    /// the state machine is left unchanged.
+    pub fn push(&mut self, byte: u8) -> Result<Option<u8>, DecodeError> {
+        if matches!(self.position, Position::Header) {
+            if byte != b'x' {
+                return Err(DecodeError::MalformedHeader);
+            }
+        } else if !is_alphabet(byte) {
+            return Err(DecodeError::InvalidByte(self.consumed));
+        }
+        let checksum = self.checksum.value();
+        let mut decoded = None;
+        self.position = match self.position {
+            Position::Header => Position::Left,
+            Position::Left => {
+                let a = index_from_vowel(byte).ok_or(DecodeError::ExpectedVowel)?;
+                // Reject vowels that cannot decode to a valid byte early
+                // instead of waiting for the rest of the triple.
+                if (a + 6 - (checksum % 6)) % 6 >= 4 {
+                    return Err(DecodeError::Corrupted);
+                }
+                Position::Mid { a }
+            }
+            Position::Mid { a } if byte == b'x' => {
+                if a != checksum % 6 {
+                    return Err(DecodeError::ChecksumMismatch);
+                }
+                Position::ChecksumRight
+            }
+            Position::Mid { a } => {
+                let b = index_from_consonant(byte).ok_or(DecodeError::ExpectedConsonant)?;
+                Position::Right { a, b }
+            }
+            Position::Right { a, b } => {
+                let c = index_from_vowel(byte).ok_or(DecodeError::ExpectedVowel)?;
+                let byte1 = decode_3_tuple(a, b, c, checksum)?;
+                self.byte1 = byte1;
+                decoded = Some(byte1);
+                Position::Up
+            }
+            Position::ChecksumRight => {
+                let c = index_from_vowel(byte).ok_or(DecodeError::ExpectedVowel)?;
+                if c != checksum / 6 {
+                    return Err(DecodeError::ChecksumMismatch);
+                }
+                Position::Trailer
+            }
+            Position::Up | Position::Trailer if byte == b'x' => Position::Complete,
+            Position::Up => {
+                let up = index_from_consonant(byte).ok_or(DecodeError::ExpectedConsonant)?;
+                Position::Separator { up }
+            }
+            Position::Separator { up } if byte == b'-' => Position::Down { up },
+            Position::Down { up } => {
+                let down = index_from_consonant(byte).ok_or(DecodeError::ExpectedConsonant)?;
+                let byte2 = (up << 4) | down;
+                self.checksum = self.checksum.update([self.byte1, byte2]);
+                decoded = Some(byte2);
+                Position::Left
+            }
+            Position::Trailer => return Err(DecodeError::MalformedTrailer),
+            Position::Separator { .. } | Position::Complete => return Err(DecodeError::Corrupted),
+        };
+        self.consumed += 1;
+        Ok(decoded)
+    }
+}
+
+/// An incremental Bubble Babble decoder for validating input as it is typed.
+///
+/// `PartialDecoder` accepts a possibly incomplete prefix of an encoding one
+/// byte at a time. Input is rejected as soon as it can no longer be extended
+/// to a valid encoding, so every prefix accepted by the decoder can be
+/// completed. After each byte, the decoder reports which characters may come
+/// next, including which vowels are consistent with the running checksum,
+/// and the bytes decoded so far.
+///
+/// # Examples
+///
+/// ```
+/// use boba::{CharClass, DecodeError, PartialDecoder};
+///
+/// # fn example() -> Result<(), DecodeError> {
+/// let mut decoder = PartialDecoder::new();
+/// decoder.feed("xigak-nyryk-hu")?;
+/// assert_eq!(decoder.decoded(), b"Pine");
+///
+/// // The next character completes the vowel-consonant-vowel triple and only
+/// // vowels consistent with the running checksum are allowed.
+/// let allowed = decoder.allowed();
+/// assert!(allowed.accepts(CharClass::Consonant));
+/// assert!(!allowed.accepts(CharClass::Vowel));
+///
+/// decoder.feed("m")?;
+/// let vowels = decoder.allowed().iter().collect::<Vec<_>>();
+/// assert_eq!(vowels, b"eiou");
+///
+/// // Rejected input leaves the decoder unchanged.
+/// assert_eq!(decoder.push(b'y'), Err(DecodeError::Corrupted));
+/// assert_eq!(decoder.consumed(), 15);
+///
+/// decoder.feed("il-bosek-sonax")?;
+/// assert!(decoder.is_complete());
+/// assert_eq!(decoder.finish()?, b"Pineapple");
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PartialDecoder {
+    machine: Machine,
+    decoded: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl PartialDecoder {
+    /// Create a new decoder that expects the leading `x` header.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            machine: Machine::new(),
+            decoded: Vec::new(),
+        }
+    }
+
+    /// Push one byte of encoded input.
+    ///
+    /// # Errors
+    ///
+    /// If `byte` cannot extend the input consumed so far to a valid encoding,
+    /// a [`DecodeError`] is returned and the decoder is left unchanged.
+    /// Vowels that are inconsistent with the running checksum are reported as
+    /// [`DecodeError::Corrupted`] or [`DecodeError::ChecksumMismatch`].
+    pub fn push(&mut self, byte: u8) -> Result<(), DecodeError> {
+        if let Some(decoded) = self.machine.push(byte)? {
+            self.decoded.push(decoded);
+        }
+        Ok(())
+    }
+
+    /// Push a slice of encoded input.
+    ///
+    /// # Errors
+    ///
+    /// If a byte cannot extend the input consumed so far to a valid encoding,
+    /// a [`DecodeError`] is returned. All bytes before the rejected byte are
+    /// consumed. Use [`consumed`](Self::consumed) to find the position of the
+    /// rejected byte.
+    pub fn feed<T: AsRef<[u8]>>(&mut self, input: T) -> Result<(), DecodeError> {
+        input.as_ref().iter().try_for_each(|&byte| self.push(byte))
+    }
+
+    /// Return the set of characters that may come next.
+    ///
+    /// The set is empty once the encoding is complete.
+    #[must_use]
+    pub fn allowed(&self) -> Allowed {
+        self.machine.allowed()
+    }
+
+    /// Return whether a complete encoding, including the trailing `x`, has been
+    /// consumed.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.machine.is_complete()
+    }
+
+    /// Return the number of bytes of encoded input consumed so far.
+    #[must_use]
+    pub fn consumed(&self) -> usize {
+        self.machine.consumed()
+    }
+
+    /// Return the bytes decoded so far.
+    ///
+    /// Only fully decoded bytes are returned. Bytes that are partially encoded
+    /// by the input consumed so far are omitted.
+    #[must_use]
+    pub fn decoded(&self) -> &[u8] {
+        &self.decoded
+    }
+
+    /// Consume the decoder and return the decoded bytes.
+    ///
+    /// # Errors
+    ///
+    /// If the encoding is not complete, [`DecodeError::MalformedTrailer`] is
+    /// returned.
+    pub fn finish(self) -> Result<Vec<u8>, DecodeError> {
+        if self.is_complete() {
+            Ok(self.decoded)
+        } else {
+            Err(DecodeError::MalformedTrailer)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn feed(input: &str) -> Result<PartialDecoder, DecodeError> {
+        let mut decoder = PartialDecoder::new();
+        decoder.feed(input)?;
+        Ok(decoder)
+    }
+
+    #[test]
+    fn test_allowed_classes() {
+        let decoder = PartialDecoder::new();
+        assert_eq!(decoder.allowed().iter().collect::<Vec<_>>(), b"x");
+
+        let decoder = feed("x").unwrap();
+        assert!(decoder.allowed().accepts(CharClass::Vowel));
+        assert_eq!(decoder.allowed().len(), 4);
+
+        let decoder = feed("xi").unwrap();
+        assert!(decoder.allowed().accepts(CharClass::Consonant));
+        assert!(!decoder.allowed().accepts(CharClass::X));
+
+        let decoder = feed("xe").unwrap();
+        assert!(decoder.allowed().accepts(CharClass::Consonant));
+        assert!(decoder.allowed().accepts(CharClass::X));
+
+        let decoder = feed("xex").unwrap();
+        assert_eq!(decoder.allowed().iter().collect::<Vec<_>>(), b"a");
+
+        let decoder = feed("xigak").unwrap();
+        assert_eq!(decoder.allowed().iter().collect::<Vec<_>>(), b"-");
+
+        let decoder = feed("xiga").unwrap();
+        assert!(decoder.allowed().accepts(CharClass::Consonant));
+        assert!(decoder.allowed().accepts(CharClass::X));
+
+        let decoder = feed("xexax").unwrap();
+        assert!(decoder.allowed().is_empty());
+    }
+
+    #[test]
+    fn test_decoded_so_far() {
+        let encoded = "xigak-nyryk-humil-bosek-sonax";
+        let mut decoder = PartialDecoder::new();
+        let mut lengths = Vec::new();
+        for byte in encoded.bytes() {
+            decoder.push(byte).unwrap();
+            lengths.push(decoder.decoded().len());
+        }
+        assert_eq!(
+            lengths,
+            [
+                0, 0, 0, 1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 6, 7, 7, 7, 8, 8, 8, 9,
+                9
+            ]
+        );
+        assert_eq!(decoder.decoded(), b"Pineapple");
+        assert!(decoder.is_complete());
+    }
+
+    #[test]
+    fn test_rejected_input_leaves_state_unchanged() {
+        let mut decoder = feed("xigak-ny").unwrap();
+        let before = decoder.clone();
+        assert_eq!(decoder.push(b'-'), Err(DecodeError::ExpectedConsonant));
+        assert_eq!(decoder.push(b'Q'), Err(DecodeError::InvalidByte(8)));
+        assert_eq!(decoder, before);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(feed("y").unwrap_err(), DecodeError::MalformedHeader);
+        assert_eq!(feed("xb").unwrap_err(), DecodeError::ExpectedVowel);
+        assert_eq!(feed("xee").unwrap_err(), DecodeError::ExpectedConsonant);
+        assert_eq!(feed("xix").unwrap_err(), DecodeError::ChecksumMismatch);
+        assert_eq!(feed("xexe").unwrap_err(), DecodeError::ChecksumMismatch);
+        assert_eq!(feed("xexab").unwrap_err(), DecodeError::MalformedTrailer);
+        assert_eq!(feed("xexaxx").unwrap_err(), DecodeError::Corrupted);
+        assert_eq!(feed("xigakx").unwrap_err(), DecodeError::Corrupted);
+        assert_eq!(feed("xabab-bx").unwrap_err(), DecodeError::Corrupted);
+        assert_eq!(feed("x💎").unwrap_err(), DecodeError::InvalidByte(1));
+    }
+
+    #[test]
+    fn test_finish_incomplete() {
+        assert_eq!(
+            feed("xigak-nyryk").unwrap().finish(),
+            Err(DecodeError::MalformedTrailer)
+        );
+        assert_eq!(feed("xexax").unwrap().finish(), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_allowed_matches_push() {
+        // At every prefix of a set of valid encodings, a byte is accepted by
+        // `push` if and only if it is in the allowed set.
+        for data in [
+            &b""[..],
+            b"\x00",
+            b"\xFF\x00\x01",
+            b"1234567890",
+            b"Pineapple",
+        ] {
+            let encoded = crate::encode::inner(data);
+            let mut decoder = PartialDecoder::new();
+            for next in encoded.bytes() {
+                let allowed = decoder.allowed();
+                for byte in 0..=u8::MAX {
+                    let mut probe = decoder.clone();
+                    assert_eq!(probe.push(byte).is_ok(), allowed.contains(byte));
+                }
+                decoder.push(next).unwrap();
+            }
+            assert_eq!(decoder.finish().unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_accepts_only_valid_encodings() {
+        // Every accepted complete encoding of a mutated valid encoding decodes
+        // to the same bytes with `decode`.
+        let encoded = crate::encode::inner(b"\x12\x34\x56");
+        for pos in 0..encoded.len() {
+            for &byte in ALPHABET {
+                let mut mutated = encoded.clone().into_bytes();
+                mutated[pos] = byte;
+                let mut decoder = PartialDecoder::new();
+                if decoder.feed(&mutated).is_ok() && decoder.is_complete() {
+                    assert_eq!(
+                        crate::decode::inner(&mutated).as_deref(),
+                        Ok(decoder.decoded())
+                    );
+                }
+            }
+        }
+    }
+}
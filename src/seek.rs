@@ -0,0 +1,659 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::decode::{decode_tail, decode_words};
+use crate::{Checksum, DecodeError};
+
+/// The default number of words between checkpoints.
+///
+/// Each checkpoint costs one byte in the index and every read decodes at most
+/// this many words on either side of the requested range.
+const DEFAULT_INTERVAL: u64 = 4096;
+
+/// Identifies a serialized [`CheckpointIndex`] and its format version.
+const INDEX_MAGIC: &[u8; 8] = b"bobaidx1";
+
+/// A sparse index of the running checksum of a Bubble Babble encoding.
+///
+/// The index records the checksum state at the start of every `interval`-th
+/// word and after the last word. A [`SeekableDecoder`] uses it to start
+/// decoding at any checkpoint and to verify what it decodes against the
+/// next one.
+///
+/// Building an index requires decoding the entire encoding. Indexes can be
+/// saved with [`write_to`](Self::write_to) and loaded with
+/// [`read_from`](Self::read_from) to skip this step.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CheckpointIndex {
+    interval: u64,
+    len: u64,
+    // The checksum state before pair `min(i * interval, pairs)` for every
+    // checkpoint `i`. The last entry is the state after the last pair.
+    checkpoints: Vec<Checksum>,
+}
+
+impl CheckpointIndex {
+    /// Return the number of words between checkpoints.
+    #[must_use]
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Return the length of the decoded data.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Return whether the decoded data is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the length of the encoding this index was built for.
+    #[must_use]
+    pub fn encoded_len(&self) -> u64 {
+        6 * self.pairs() + 5
+    }
+
+    /// Return the checksum state at every checkpoint.
+    ///
+    /// Checkpoint `i` is the state before word `i * interval`. The final
+    /// checkpoint is the state after the last pair of bytes.
+    #[must_use]
+    pub fn checkpoints(&self) -> &[Checksum] {
+        &self.checkpoints
+    }
+
+    /// Serialize the index to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// If writing to `writer` fails, the error is returned.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(INDEX_MAGIC)?;
+        writer.write_all(&self.interval.to_le_bytes())?;
+        writer.write_all(&self.len.to_le_bytes())?;
+        let checkpoints = self
+            .checkpoints
+            .iter()
+            .map(|checksum| checksum.value())
+            .collect::<Vec<_>>();
+        writer.write_all(&checkpoints)
+    }
+
+    /// Deserialize an index written by [`write_to`](Self::write_to) from
+    /// `reader`.
+    ///
+    /// # Errors
+    ///
+    /// If reading from `reader` fails, the error is returned. If the data is
+    /// not a valid index, an error of kind [`InvalidData`] is returned.
+    ///
+    /// [`InvalidData`]: io::ErrorKind::InvalidData
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != INDEX_MAGIC {
+            return Err(invalid_index("unrecognized checkpoint index format"));
+        }
+        let interval = read_u64(&mut reader)?;
+        let len = read_u64(&mut reader)?;
+        if interval == 0 {
+            return Err(invalid_index("checkpoint interval must be positive"));
+        }
+        let count = (len / 2).div_ceil(interval) + 1;
+        // The header is untrusted, so read through a bounded reader instead
+        // of allocating `count` bytes up front.
+        let mut checkpoints = Vec::new();
+        reader.take(count).read_to_end(&mut checkpoints)?;
+        if u64::try_from(checkpoints.len()) != Ok(count) {
+            return Err(invalid_index("checkpoint index is truncated"));
+        }
+        let checkpoints = checkpoints
+            .into_iter()
+            .map(Checksum::from_value)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| invalid_index("checkpoint is not a checksum state"))?;
+        if checkpoints[0] != Checksum::initial() {
+            return Err(invalid_index("first checkpoint is not the initial state"));
+        }
+        Ok(Self {
+            interval,
+            len,
+            checkpoints,
+        })
+    }
+
+    fn pairs(&self) -> u64 {
+        self.len / 2
+    }
+}
+
+/// A decoder that serves random-access reads of the data in a Bubble Babble
+/// encoding.
+///
+/// The decoder keeps a [`CheckpointIndex`] of the running checksum. Reads seek
+/// to the checkpoint before the requested range and decode forward to the
+/// checkpoint after it. The checksum at the end of every decoded run of words
+/// is verified against the index, so corrupted or modified input is detected
+/// even though the encoding is never decoded in full again.
+///
+/// `SeekableDecoder` implements [`Read`] and [`Seek`] over the decoded data.
+/// Use [`read_at`](Self::read_at) to read without moving the cursor.
+///
+/// The last decoded block is kept, so consecutive small reads, like those of
+/// [`Read::read_to_end`], decode each block once.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{Cursor, Read, Seek, SeekFrom};
+/// use boba::SeekableDecoder;
+///
+/// # fn example() -> std::io::Result<()> {
+/// let data = (0..=255).cycle().take(10_000).collect::<Vec<u8>>();
+/// let encoded = Cursor::new(boba::encode(&data));
+///
+/// let mut decoder = SeekableDecoder::with_interval(encoded, 64)?;
+/// assert_eq!(decoder.len(), 10_000);
+///
+/// let mut buf = [0; 4];
+/// decoder.read_at(5_001, &mut buf)?;
+/// assert_eq!(buf, [137, 138, 139, 140]);
+///
+/// decoder.seek(SeekFrom::End(-3))?;
+/// let mut tail = Vec::new();
+/// decoder.read_to_end(&mut tail)?;
+/// assert_eq!(tail, [13, 14, 15]);
+/// # Ok(())
+/// # }
+/// # example().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SeekableDecoder<R> {
+    inner: R,
+    index: CheckpointIndex,
+    pos: u64,
+    encoded: Vec<u8>,
+    decoded: Vec<u8>,
+    // The checkpoint whose verified block of words is held in `decoded`.
+    cached: Option<u64>,
+}
+
+impl<R: Read + Seek> SeekableDecoder<R> {
+    /// Create a decoder over the encoding in `inner` with the default
+    /// checkpoint interval.
+    ///
+    /// This decodes the entire encoding to build the [`CheckpointIndex`].
+    ///
+    /// # Errors
+    ///
+    /// If reading from `inner` fails, the error is returned. If `inner` is
+    /// not a valid Bubble Babble encoding, an error of kind [`InvalidData`]
+    /// wrapping a [`DecodeError`] is returned.
+    ///
+    /// [`InvalidData`]: io::ErrorKind::InvalidData
+    pub fn new(inner: R) -> io::Result<Self> {
+        Self::with_interval(inner, DEFAULT_INTERVAL)
+    }
+
+    /// Create a decoder over the encoding in `inner` with a checkpoint every
+    /// `interval` words.
+    ///
+    /// Smaller intervals make reads of short ranges cheaper at the cost of a
+    /// larger index.
+    ///
+    /// # Errors
+    ///
+    /// If reading from `inner` fails, the error is returned. If `inner` is
+    /// not a valid Bubble Babble encoding, an error of kind [`InvalidData`]
+    /// wrapping a [`DecodeError`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    ///
+    /// [`InvalidData`]: io::ErrorKind::InvalidData
+    pub fn with_interval(inner: R, interval: u64) -> io::Result<Self> {
+        assert!(interval > 0, "checkpoint interval must be positive");
+        let mut decoder = Self {
+            inner,
+            index: CheckpointIndex {
+                interval,
+                len: 0,
+                checkpoints: vec![Checksum::initial()],
+            },
+            pos: 0,
+            encoded: Vec::new(),
+            decoded: Vec::new(),
+            cached: None,
+        };
+        decoder.build_index()?;
+        Ok(decoder)
+    }
+
+    /// Create a decoder over the encoding in `inner` with a previously built
+    /// index.
+    ///
+    /// Only the length of the encoding is checked against the index. Reads
+    /// verify the data they decode.
+    ///
+    /// # Errors
+    ///
+    /// If seeking in `inner` fails, the error is returned. If the length of
+    /// the encoding does not match the index, an error of kind
+    /// [`InvalidData`] is returned.
+    ///
+    /// [`InvalidData`]: io::ErrorKind::InvalidData
+    pub fn with_index(mut inner: R, index: CheckpointIndex) -> io::Result<Self> {
+        let encoded_len = inner.seek(SeekFrom::End(0))?;
+        if encoded_len != index.encoded_len() {
+            return Err(invalid_index("checkpoint index does not match encoding"));
+        }
+        Ok(Self {
+            inner,
+            index,
+            pos: 0,
+            encoded: Vec::new(),
+            decoded: Vec::new(),
+            cached: None,
+        })
+    }
+
+    /// Read decoded bytes starting at `offset` into `buf` and return the
+    /// number of bytes read.
+    ///
+    /// Fewer bytes than requested are read only at the end of the data. This
+    /// does not move the cursor used by [`Read`] and [`Seek`].
+    ///
+    /// # Errors
+    ///
+    /// If reading from the inner reader fails, the error is returned. If the
+    /// encoding is invalid or inconsistent with the index, an error of kind
+    /// [`InvalidData`] wrapping a [`DecodeError`] is returned.
+    ///
+    /// [`InvalidData`]: io::ErrorKind::InvalidData
+    pub fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.index.len;
+        if offset >= len || buf.is_empty() {
+            return Ok(0);
+        }
+        let end = cmp::min(offset.saturating_add(buf.len() as u64), len);
+        let pairs = self.index.pairs();
+        let interval = self.index.interval;
+
+        let first_pair = offset / 2;
+        let end_pair = cmp::min(end.div_ceil(2), pairs);
+        if first_pair < end_pair {
+            let first_checkpoint = first_pair / interval;
+            let end_checkpoint = end_pair.div_ceil(interval);
+            for checkpoint in first_checkpoint..end_checkpoint {
+                let block_pair = checkpoint * interval;
+                let count = cmp::min(interval, pairs - block_pair);
+                if self.cached != Some(checkpoint) {
+                    let index = usize::try_from(checkpoint).map_err(|_| corrupted())?;
+                    let checksum =
+                        self.decode_block(block_pair, count, self.index.checkpoints[index])?;
+                    if checksum != self.index.checkpoints[index + 1] {
+                        return Err(DecodeError::ChecksumMismatch.into());
+                    }
+                    self.cached = Some(checkpoint);
+                }
+                // Copy the part of the block that overlaps the requested range.
+                let block_start = 2 * block_pair;
+                let block_end = block_start + 2 * count;
+                let from = cmp::max(offset, block_start);
+                let to = cmp::min(end, block_end);
+                let src = &self.decoded[to_usize(from - block_start)..to_usize(to - block_start)];
+                buf[to_usize(from - offset)..to_usize(to - offset)].copy_from_slice(src);
+            }
+        }
+        if end > 2 * pairs {
+            // The last byte of odd length data is in the final 3-tuple.
+            let checksum = *self.index.checkpoints.last().ok_or_else(corrupted)?;
+            let byte = self.decode_tail(pairs, checksum)?.ok_or_else(corrupted)?;
+            buf[to_usize(2 * pairs - offset)] = byte;
+        }
+        Ok(to_usize(end - offset))
+    }
+
+    /// Return the length of the decoded data.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.index.len
+    }
+
+    /// Return whether the decoded data is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Return the checkpoint index used by this decoder.
+    #[must_use]
+    pub fn index(&self) -> &CheckpointIndex {
+        &self.index
+    }
+
+    /// Return a reference to the inner reader.
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consume the decoder and return the inner reader.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn build_index(&mut self) -> io::Result<()> {
+        let encoded_len = self.inner.seek(SeekFrom::End(0))?;
+        // Every encoding is a header, a 6 byte word per pair, a final 3-tuple
+        // and a trailer.
+        if encoded_len < 5 || (encoded_len - 5) % 6 != 0 {
+            return Err(corrupted());
+        }
+        let pairs = (encoded_len - 5) / 6;
+        self.inner.seek(SeekFrom::Start(0))?;
+        let mut header = [0; 1];
+        self.inner.read_exact(&mut header)?;
+        if header != [b'x'] {
+            return Err(DecodeError::MalformedHeader.into());
+        }
+
+        let interval = self.index.interval;
+        let mut checksum = Checksum::initial();
+        let mut pair = 0;
+        while pair < pairs {
+            let count = cmp::min(interval, pairs - pair);
+            checksum = self.decode_block(pair, count, checksum)?;
+            self.index.checkpoints.push(checksum);
+            pair += count;
+        }
+        let tail = self.decode_tail(pairs, checksum)?;
+        self.index.len = 2 * pairs + u64::from(tail.is_some());
+        Ok(())
+    }
+
+    /// Decode `count` words starting with word `pair` into `self.decoded`
+    /// and return the checksum after them.
+    fn decode_block(&mut self, pair: u64, count: u64, checksum: Checksum) -> io::Result<Checksum> {
+        let count = to_usize(count);
+        self.cached = None;
+        self.encoded.resize(6 * count, 0);
+        self.decoded.resize(2 * count, 0);
+        self.inner.seek(SeekFrom::Start(1 + 6 * pair))?;
+        self.inner.read_exact(&mut self.encoded)?;
+        match decode_words(&self.encoded, checksum, &mut self.decoded) {
+            Ok((decoded, checksum)) if decoded == count => Ok(checksum),
+            // A word is missing its separator.
+            Ok(_) => Err(corrupted()),
+            Err(DecodeError::InvalidByte(pos)) => {
+                // `decode_words` positions are relative to the start of the
+                // block, offset by one for the header.
+                let pos = to_usize(6 * pair).saturating_add(pos);
+                Err(DecodeError::InvalidByte(pos).into())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Decode the final 3-tuple and trailer after `pairs` words.
+    fn decode_tail(&mut self, pairs: u64, checksum: Checksum) -> io::Result<Option<u8>> {
+        let mut tail = [0; 4];
+        self.inner.seek(SeekFrom::Start(1 + 6 * pairs))?;
+        self.inner.read_exact(&mut tail)?;
+        let [left, mid, right, trailer] = tail;
+        if trailer != b'x' {
+            return Err(DecodeError::MalformedTrailer.into());
+        }
+        Ok(decode_tail([left, mid, right], checksum)?)
+    }
+}
+
+impl<R: Read + Seek> Read for SeekableDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.read_at(self.pos, buf)?;
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Read + Seek> Seek for SeekableDecoder<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::End(delta) => self.index.len.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+        };
+        let pos = pos.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        self.pos = pos;
+        Ok(pos)
+    }
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn invalid_index(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn corrupted() -> io::Error {
+    DecodeError::Corrupted.into()
+}
+
+/// Convert an offset within a block or a requested range to `usize`.
+///
+/// These are bounded by the length of an in-memory buffer.
+fn to_usize(n: u64) -> usize {
+    usize::try_from(n).expect("offset is bounded by an in-memory buffer")
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::testing::data;
+
+    fn seekable(data: &[u8], interval: u64) -> SeekableDecoder<Cursor<String>> {
+        SeekableDecoder::with_interval(Cursor::new(crate::encode(data)), interval).unwrap()
+    }
+
+    fn decode_error(err: &io::Error) -> Option<DecodeError> {
+        err.get_ref()?.downcast_ref::<DecodeError>().copied()
+    }
+
+    #[test]
+    fn test_read_at_every_range() {
+        for len in [0, 1, 2, 3, 9, 10, 41, 42] {
+            let data = data(len);
+            for interval in [1, 2, 3, 100] {
+                let mut decoder = seekable(&data, interval);
+                assert_eq!(decoder.len(), len as u64);
+                for start in 0..=len {
+                    for end in start..=len + 2 {
+                        let mut buf = vec![0; end - start];
+                        let read = decoder.read_at(start as u64, &mut buf).unwrap();
+                        let expected = &data[start..end.min(len)];
+                        assert_eq!(&buf[..read], expected, "{start}..{end} of {len}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_index() {
+        let decoder = seekable(b"1234567890", 2);
+        let index = decoder.index();
+        assert_eq!(index.interval(), 2);
+        assert_eq!(index.len(), 10);
+        assert_eq!(index.encoded_len(), 35);
+        let expected = [0, 2, 4, 5]
+            .map(|pairs| Checksum::from_bytes(&b"1234567890"[..2 * pairs]))
+            .to_vec();
+        assert_eq!(index.checkpoints(), expected);
+
+        let decoder = seekable(b"", 2);
+        assert!(decoder.is_empty());
+        assert_eq!(decoder.index().checkpoints(), [Checksum::initial()]);
+    }
+
+    #[test]
+    fn test_index_roundtrip() {
+        let data = data(1000);
+        let decoder = seekable(&data, 16);
+        let mut saved = Vec::new();
+        decoder.index().write_to(&mut saved).unwrap();
+        let index = CheckpointIndex::read_from(saved.as_slice()).unwrap();
+        assert_eq!(&index, decoder.index());
+
+        let mut decoder = SeekableDecoder::with_index(decoder.into_inner(), index).unwrap();
+        let mut output = Vec::new();
+        decoder.read_to_end(&mut output).unwrap();
+        assert_eq!(output, data);
+    }
+
+    #[test]
+    fn test_read_from_rejects_invalid_index() {
+        let mut saved = Vec::new();
+        seekable(&data(100), 8)
+            .index()
+            .write_to(&mut saved)
+            .unwrap();
+
+        let mut bad_magic = saved.clone();
+        bad_magic[0] = b'B';
+        let mut bad_checksum = saved.clone();
+        bad_checksum[30] = 36;
+        let mut bad_initial = saved.clone();
+        bad_initial[24] = 2;
+        for bad in [bad_magic, bad_checksum, bad_initial] {
+            let err = CheckpointIndex::read_from(bad.as_slice()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+        let err = CheckpointIndex::read_from(&saved[..saved.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_read_from_rejects_huge_count() {
+        // A header that claims 2^33 checkpoints must not allocate them.
+        let mut crafted = INDEX_MAGIC.to_vec();
+        crafted.extend_from_slice(&1_u64.to_le_bytes());
+        crafted.extend_from_slice(&(1_u64 << 34).to_le_bytes());
+        crafted.push(Checksum::initial().value());
+        let err = CheckpointIndex::read_from(crafted.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_small_reads_reuse_block() {
+        let data = data(1000);
+        let mut decoder = seekable(&data, 64);
+        let mut byte = [0; 1];
+        for expected in &data[..200] {
+            decoder.read_exact(&mut byte).unwrap();
+            assert_eq!(byte[0], *expected);
+        }
+        assert_eq!(decoder.cached, Some(1));
+        decoder.seek(SeekFrom::Start(990)).unwrap();
+        let mut rest = Vec::new();
+        decoder.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, data[990..]);
+    }
+
+    #[test]
+    fn test_with_index_rejects_other_encoding() {
+        let index = seekable(&data(100), 8).index().clone();
+        let other = Cursor::new(crate::encode(data(102)));
+        let err = SeekableDecoder::with_index(other, index).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_read_and_seek() {
+        let data = data(100);
+        let mut decoder = seekable(&data, 4);
+        let mut buf = [0; 10];
+        assert_eq!(decoder.seek(SeekFrom::Start(45)).unwrap(), 45);
+        decoder.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, data[45..55]);
+        assert_eq!(decoder.seek(SeekFrom::Current(-20)).unwrap(), 35);
+        decoder.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, data[35..45]);
+        assert_eq!(decoder.seek(SeekFrom::End(-5)).unwrap(), 95);
+        let mut rest = Vec::new();
+        decoder.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, data[95..]);
+        assert_eq!(decoder.seek(SeekFrom::End(10)).unwrap(), 110);
+        assert_eq!(decoder.read(&mut buf).unwrap(), 0);
+        let err = decoder.seek(SeekFrom::Current(-111)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_corruption_after_indexing_is_detected() {
+        let data = data(100);
+        let decoder = seekable(&data, 4);
+        let index = decoder.index().clone();
+        let mut encoded = decoder.into_inner().into_inner().into_bytes();
+        // Swap the consonants around the separator of word 11, which changes the
+        // data without making the word invalid on its own.
+        encoded.swap(1 + 6 * 11 + 3, 1 + 6 * 11 + 5);
+        assert_ne!(crate::decode(&encoded).as_deref(), Ok(&data[..]));
+
+        let mut decoder = SeekableDecoder::with_index(Cursor::new(encoded), index).unwrap();
+        let mut buf = [0; 4];
+        // Words 8 through 11 are verified against the checkpoint at word 12.
+        let err = decoder.read_at(18, &mut buf).unwrap_err();
+        assert_eq!(decode_error(&err), Some(DecodeError::ChecksumMismatch));
+        // Other blocks are still readable.
+        decoder.read_at(0, &mut buf).unwrap();
+        assert_eq!(buf, data[..4]);
+        decoder.read_at(90, &mut buf).unwrap();
+        assert_eq!(buf, data[90..94]);
+    }
+
+    #[test]
+    fn test_invalid_encoding() {
+        let cases: [(&[u8], DecodeError); 5] = [
+            (
+                b"yigak-nyryk-humil-bosek-sonax",
+                DecodeError::MalformedHeader,
+            ),
+            (
+                b"xigak-nyryk-humil-bosek-sonay",
+                DecodeError::MalformedTrailer,
+            ),
+            (b"xigak-nyryk-humil-bosek-sonx", DecodeError::Corrupted),
+            (
+                b"xigak-nyryk-hu!il-bosek-sonax",
+                DecodeError::InvalidByte(14),
+            ),
+            (b"xigak-nyryk-humil-bosek-sonox", DecodeError::Corrupted),
+        ];
+        for (encoded, expected) in cases {
+            let err = SeekableDecoder::with_interval(Cursor::new(encoded), 2).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(decode_error(&err), Some(expected), "{encoded:?}");
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! Serialize bytes as Bubble Babble encodings with [`serde`].
+//!
+//! This module is meant to be used with `#[serde(with = "boba::serde")]` on
+//! fields of any type that implements [`AsRef<[u8]>`](AsRef) and
+//! [`TryFrom<Vec<u8>>`](TryFrom), like [`Vec<u8>`](Vec) and `[u8; N]`. Use
+//! [`boba::serde::option`](option) for optional fields.
+//!
+//! Human-readable formats like JSON store the Bubble Babble encoding as a
+//! string. Compact formats like bincode store the raw bytes instead.
+//! Deserializing an invalid encoding returns an error with the
+//! [`DecodeError`] details.
+//!
+//! With this module enabled, [`Encoder`] implements [`Serialize`] and
+//! [`Deserialize`] as its encoding, and [`BubbleBabbleDisplay`] implements
+//! [`Serialize`].
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Key {
+//!     #[serde(with = "boba::serde")]
+//!     fingerprint: [u8; 9],
+//!     #[serde(with = "boba::serde::option")]
+//!     signature: Option<Vec<u8>>,
+//! }
+//!
+//! # fn example() -> Result<(), serde_json::Error> {
+//! let key = Key {
+//!     fingerprint: *b"Pineapple",
+//!     signature: None,
+//! };
+//! let json = serde_json::to_string(&key)?;
+//! assert_eq!(
+//!     json,
+//!     r#"{"fingerprint":"xigak-nyryk-humil-bosek-sonax","signature":null}"#
+//! );
+//! assert_eq!(serde_json::from_str::<Key>(&json)?, key);
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+//!
+//! [`Serialize`]: ::serde::Serialize
+//! [`Deserialize`]: ::serde::Deserialize
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use ::serde::de::{self, Deserializer, SeqAccess, Visitor};
+use ::serde::{Deserialize, Serialize, Serializer};
+
+use crate::{BubbleBabbleDisplay, DecodeError, Encoder};
+
+/// Serialize bytes as a Bubble Babble encoding.
+///
+/// Compact formats store the raw bytes instead.
+///
+/// # Errors
+///
+/// Errors from the serializer are returned.
+pub fn serialize<T, S>(data: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<[u8]> + ?Sized,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&BubbleBabbleDisplay::new(data.as_ref()))
+    } else {
+        serializer.serialize_bytes(data.as_ref())
+    }
+}
+
+/// Deserialize bytes from a Bubble Babble encoding.
+///
+/// Compact formats store the raw bytes instead.
+///
+/// # Errors
+///
+/// If the input is not a valid encoding, an error with the [`DecodeError`]
+/// details is returned. If the decoded bytes do not fit in `T`, like a
+/// `[u8; N]` of a different length, an invalid length error is returned.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: TryFrom<Vec<u8>>,
+    D: Deserializer<'de>,
+{
+    let data = if deserializer.is_human_readable() {
+        deserializer.deserialize_str(EncodedVisitor)?
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)?
+    };
+    let len = data.len();
+    T::try_from(data).map_err(|_| de::Error::invalid_length(len, &"bytes that fit the field"))
+}
+
+/// Serialize optional bytes as Bubble Babble encodings with [`serde`].
+///
+/// This module is meant to be used with
+/// `#[serde(with = "boba::serde::option")]` on `Option` fields of the types
+/// supported by [`boba::serde`](super).
+pub mod option {
+    use alloc::vec::Vec;
+    use core::fmt;
+    use core::marker::PhantomData;
+
+    use ::serde::de::{Deserializer, Visitor};
+    use ::serde::{Serialize, Serializer};
+
+    /// Serialize optional bytes as a Bubble Babble encoding.
+    ///
+    /// # Errors
+    ///
+    /// Errors from the serializer are returned.
+    pub fn serialize<T, S>(data: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        struct Bytes<'a>(&'a [u8]);
+
+        impl Serialize for Bytes<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                super::serialize(self.0, serializer)
+            }
+        }
+
+        match data {
+            Some(data) => serializer.serialize_some(&Bytes(data.as_ref())),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize optional bytes from a Bubble Babble encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`boba::serde::deserialize`](super::deserialize).
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        struct OptionVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: TryFrom<Vec<u8>>> Visitor<'de> for OptionVisitor<T> {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an optional Bubble Babble encoding")
+            }
+
+            fn visit_none<E>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                super::deserialize(deserializer).map(Some)
+            }
+        }
+
+        deserializer.deserialize_option(OptionVisitor(PhantomData))
+    }
+}
+
+fn decode_error<E: de::Error>(err: DecodeError) -> E {
+    E::custom(format_args!("invalid Bubble Babble encoding: {err}"))
+}
+
+/// Decode a Bubble Babble encoded string.
+struct EncodedVisitor;
+
+impl Visitor<'_> for EncodedVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a Bubble Babble encoded string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        crate::decode(v).map_err(decode_error)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        crate::decode(v).map_err(decode_error)
+    }
+}
+
+/// Collect raw bytes from compact formats.
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut data = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            data.push(byte);
+        }
+        Ok(data)
+    }
+}
+
+impl Serialize for BubbleBabbleDisplay<'_> {
+    /// Serialize the encoding as a string in all formats.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for Encoder {
+    /// Serialize the encoding of the data appended so far as a string in all
+    /// formats.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.finish())
+    }
+}
+
+impl<'de> Deserialize<'de> for Encoder {
+    /// Deserialize an encoder that [resumes](Encoder::resume) an encoding.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EncoderVisitor;
+
+        impl Visitor<'_> for EncoderVisitor {
+            type Value = Encoder;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a Bubble Babble encoded string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Encoder::resume(v).map_err(decode_error)
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Encoder::resume(v).map_err(decode_error)
+            }
+        }
+
+        deserializer.deserialize_str(EncoderVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use ::serde::{Deserialize, Serialize};
+    use serde_test::{assert_de_tokens_error, assert_ser_tokens, assert_tokens, Configure, Token};
+
+    use crate::Encoder;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Key {
+        #[serde(with = "crate::serde")]
+        fingerprint: [u8; 9],
+        #[serde(with = "crate::serde")]
+        data: Vec<u8>,
+        #[serde(with = "crate::serde::option")]
+        signature: Option<Vec<u8>>,
+    }
+
+    fn key(signature: Option<Vec<u8>>) -> Key {
+        Key {
+            fingerprint: *b"Pineapple",
+            data: b"1234567890".to_vec(),
+            signature,
+        }
+    }
+
+    #[test]
+    fn test_readable() {
+        assert_tokens(
+            &key(Some(vec![])).readable(),
+            &[
+                Token::Struct {
+                    name: "Key",
+                    len: 3,
+                },
+                Token::Str("fingerprint"),
+                Token::Str("xigak-nyryk-humil-bosek-sonax"),
+                Token::Str("data"),
+                Token::Str("xesef-disof-gytuf-katof-movif-baxux"),
+                Token::Str("signature"),
+                Token::Some,
+                Token::Str("xexax"),
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &key(None).readable(),
+            &[
+                Token::Struct {
+                    name: "Key",
+                    len: 3,
+                },
+                Token::Str("fingerprint"),
+                Token::Str("xigak-nyryk-humil-bosek-sonax"),
+                Token::Str("data"),
+                Token::Str("xesef-disof-gytuf-katof-movif-baxux"),
+                Token::Str("signature"),
+                Token::None,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        assert_tokens(
+            &key(Some(b"xyz!x6".to_vec())).compact(),
+            &[
+                Token::Struct {
+                    name: "Key",
+                    len: 3,
+                },
+                Token::Str("fingerprint"),
+                Token::Bytes(b"Pineapple"),
+                Token::Str("data"),
+                Token::Bytes(b"1234567890"),
+                Token::Str("signature"),
+                Token::Some,
+                Token::Bytes(b"xyz!x6"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_de_tokens_error::<serde_test::Readable<Key>>(
+            &[
+                Token::Struct {
+                    name: "Key",
+                    len: 3,
+                },
+                Token::Str("fingerprint"),
+                Token::Str("xesef-disof-gytuf-katof-movif-baxix"),
+            ],
+            "invalid Bubble Babble encoding: Checksum mismatch",
+        );
+        assert_de_tokens_error::<serde_test::Readable<Key>>(
+            &[
+                Token::Struct {
+                    name: "Key",
+                    len: 3,
+                },
+                Token::Str("fingerprint"),
+                Token::Str("xesef-disof-gytuf-katof-movif-baxux"),
+            ],
+            "invalid length 10, expected bytes that fit the field",
+        );
+        assert_de_tokens_error::<serde_test::Compact<Key>>(
+            &[
+                Token::Struct {
+                    name: "Key",
+                    len: 3,
+                },
+                Token::Str("fingerprint"),
+                Token::Bytes(b"Pine"),
+            ],
+            "invalid length 4, expected bytes that fit the field",
+        );
+    }
+
+    #[test]
+    fn test_encoder() {
+        let mut encoder = Encoder::new();
+        encoder.update("Pineapple");
+        let tokens = [Token::Str("xigak-nyryk-humil-bosek-sonax")];
+        assert_tokens(&encoder.clone().readable(), &tokens);
+        assert_tokens(&encoder.compact(), &tokens);
+        assert_de_tokens_error::<Encoder>(
+            &[Token::Str("xesef-disof-gytuf-katof-movif-baxix")],
+            "invalid Bubble Babble encoding: Checksum mismatch",
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_ser_tokens(
+            &crate::display("Pineapple"),
+            &[Token::Str("xigak-nyryk-humil-bosek-sonax")],
+        );
+    }
+}
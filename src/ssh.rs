@@ -0,0 +1,330 @@
+//! Bubble Babble fingerprints of OpenSSH public keys.
+//!
+//! The Bubble Babble encoding was created at SSH Communications Security,
+//! and `ssh-keygen -B` still prints the Bubble Babble encoding of the SHA-1
+//! hash of a public key. This module parses the public key lines of OpenSSH
+//! `*.pub` and `authorized_keys` files and computes the same fingerprints, so
+//! a fingerprint read aloud or pasted from another machine can be checked
+//! against a key file.
+//!
+//! # Examples
+//!
+//! ```
+//! use boba::ssh::{self, PublicKey};
+//!
+//! # fn example() -> Result<(), boba::ssh::ParseKeyError> {
+//! let line = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIOm3oBdO7t4QKak2ycGBIdqRurqFH1x8lweEtvPLrsFV alice@example.com";
+//! let key = line.parse::<PublicKey>()?;
+//! assert_eq!(key.key_type(), "ssh-ed25519");
+//! assert_eq!(key.comment(), Some("alice@example.com"));
+//! assert_eq!(
+//!     key.fingerprint(),
+//!     "xufev-lyhyl-hikys-lahur-gylop-huhit-zonal-nuzut-lymyf-nycob-ruxex"
+//! );
+//!
+//! let authorized_keys = format!("# deploy keys\nno-pty {line}\n");
+//! let found = ssh::find_key(
+//!     &authorized_keys,
+//!     "xufev-lyhyl-hikys-lahur-gylop-huhit-zonal-nuzut-lymyf-nycob-ruxex",
+//! );
+//! assert_eq!(found, Some(key));
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::base64;
+use crate::fingerprint;
+
+/// An OpenSSH public key parsed from a line of a `*.pub` or
+/// `authorized_keys` file.
+///
+/// The key is kept in its wire format. Only the key type embedded in it is
+/// checked, so keys of types unknown to this crate can still be
+/// fingerprinted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PublicKey {
+    key_type: String,
+    blob: Vec<u8>,
+    comment: Option<String>,
+}
+
+impl PublicKey {
+    /// Parse a public key line.
+    ///
+    /// The line has the form `type base64-key [comment]`, optionally preceded
+    /// by the comma-separated options of an `authorized_keys` line.
+    ///
+    /// # Errors
+    ///
+    /// If the line does not contain a valid public key, an error is returned.
+    pub fn parse(line: &str) -> Result<Self, ParseKeyError> {
+        let line = line.trim();
+        let err = match parse_key(line) {
+            Ok(key) => return Ok(key),
+            Err(err) => err,
+        };
+        let Some(rest) = skip_options(line) else {
+            return Err(err);
+        };
+        // Report the error of the first attempt if the line has no key after
+        // the supposed options.
+        match parse_key(rest) {
+            Err(ParseKeyError::MissingKeyType | ParseKeyError::MissingKey) => Err(err),
+            result => result,
+        }
+    }
+
+    /// Return the key type, like `ssh-ed25519` or `ssh-rsa`.
+    #[must_use]
+    pub fn key_type(&self) -> &str {
+        &self.key_type
+    }
+
+    /// Return the key in the SSH wire format, which is the base64-decoded
+    /// second field of the key line.
+    #[must_use]
+    pub fn blob(&self) -> &[u8] {
+        &self.blob
+    }
+
+    /// Return the comment after the key, if any.
+    #[must_use]
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Return the Bubble Babble encoding of the SHA-1 hash of the key, which
+    /// is the fingerprint printed by `ssh-keygen -B`.
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        fingerprint::sha1(&self.blob)
+    }
+
+    /// Return whether `fingerprint` is the `ssh-keygen -B` fingerprint of
+    /// the key.
+    ///
+    /// Surrounding whitespace and the case of letters are ignored.
+    #[must_use]
+    pub fn verify(&self, fingerprint: &str) -> bool {
+        self.fingerprint().eq_ignore_ascii_case(fingerprint.trim())
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for PublicKey {
+    /// Format the key as a `*.pub` file line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.key_type)?;
+        f.write_str(" ")?;
+        base64::encode(&self.blob, f)?;
+        if let Some(comment) = &self.comment {
+            write!(f, " {comment}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the key in the contents of a `*.pub` or `authorized_keys` file whose
+/// `ssh-keygen -B` fingerprint is `fingerprint`.
+///
+/// Blank lines and lines starting with `#` are skipped. Like `sshd` and
+/// `ssh-keygen`, lines that do not contain a valid key are also skipped.
+/// The fingerprint is compared like [`PublicKey::verify`].
+#[must_use]
+pub fn find_key(contents: &str, fingerprint: &str) -> Option<PublicKey> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| PublicKey::parse(line).ok())
+        .find(|key| key.verify(fingerprint))
+}
+
+fn parse_key(line: &str) -> Result<PublicKey, ParseKeyError> {
+    let (key_type, rest) = split_field(line).ok_or(ParseKeyError::MissingKeyType)?;
+    let (encoded, comment) = split_field(rest).ok_or(ParseKeyError::MissingKey)?;
+    let blob = base64::decode(encoded.as_bytes()).map_err(|_| ParseKeyError::InvalidBase64)?;
+    // The key starts with its type as an SSH string.
+    let (len, data) = blob
+        .split_first_chunk::<4>()
+        .ok_or(ParseKeyError::MalformedKey)?;
+    let len = usize::try_from(u32::from_be_bytes(*len)).map_err(|_| ParseKeyError::MalformedKey)?;
+    let embedded = data.get(..len).ok_or(ParseKeyError::MalformedKey)?;
+    if embedded != key_type.as_bytes() {
+        return Err(ParseKeyError::KeyTypeMismatch);
+    }
+    Ok(PublicKey {
+        key_type: key_type.to_string(),
+        blob,
+        comment: Some(comment)
+            .filter(|comment| !comment.is_empty())
+            .map(ToString::to_string),
+    })
+}
+
+/// Split the first whitespace-separated field from `line`.
+fn split_field(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start();
+    if line.is_empty() {
+        return None;
+    }
+    let (field, rest) = line.split_at(line.find(char::is_whitespace).unwrap_or(line.len()));
+    Some((field, rest.trim()))
+}
+
+/// Skip the options at the start of an `authorized_keys` line.
+///
+/// Options are separated by commas and may contain quoted strings with
+/// spaces, like `command="echo hello"`.
+fn skip_options(line: &str) -> Option<&str> {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (pos, ch) in line.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ch if ch.is_whitespace() && !quoted => return Some(&line[pos..]),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Errors from parsing an OpenSSH public key line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseKeyError {
+    /// The line is empty.
+    MissingKeyType,
+    /// The line has no key after the key type.
+    MissingKey,
+    /// The key is not valid base64.
+    InvalidBase64,
+    /// The key does not start with a key type.
+    MalformedKey,
+    /// The key type in the key does not match the key type of the line.
+    KeyTypeMismatch,
+}
+
+impl core::error::Error for ParseKeyError {}
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingKeyType => f.write_str("Missing key type"),
+            Self::MissingKey => f.write_str("Missing base64-encoded key"),
+            Self::InvalidBase64 => f.write_str("Key is not valid base64"),
+            Self::MalformedKey => f.write_str("Key does not start with its key type"),
+            Self::KeyTypeMismatch => f.write_str("Key type does not match the key"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519: &str = include_str!("../tests/fixtures/ssh/ed25519.pub");
+    const RSA: &str = include_str!("../tests/fixtures/ssh/rsa.pub");
+    const ECDSA: &str = include_str!("../tests/fixtures/ssh/ecdsa.pub");
+    const AUTHORIZED_KEYS: &str = include_str!("../tests/fixtures/ssh/authorized_keys");
+
+    /// Return the fingerprints in the output of `ssh-keygen -B -f`.
+    fn ssh_keygen(output: &str) -> Vec<&str> {
+        output
+            .lines()
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .collect()
+    }
+
+    #[test]
+    fn test_fingerprints_match_ssh_keygen() {
+        let fixtures = [
+            (
+                ED25519,
+                include_str!("../tests/fixtures/ssh/ed25519.pub.bubblebabble"),
+            ),
+            (
+                RSA,
+                include_str!("../tests/fixtures/ssh/rsa.pub.bubblebabble"),
+            ),
+            (
+                ECDSA,
+                include_str!("../tests/fixtures/ssh/ecdsa.pub.bubblebabble"),
+            ),
+        ];
+        for (line, output) in fixtures {
+            let key = PublicKey::parse(line).unwrap();
+            assert_eq!([key.fingerprint().as_str()], *ssh_keygen(output));
+        }
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let key = PublicKey::parse(RSA).unwrap();
+        assert_eq!(key.key_type(), "ssh-rsa");
+        assert_eq!(key.comment(), Some("bob@example.com"));
+        assert_eq!(key.to_string(), RSA.trim());
+
+        let key = PublicKey::parse(ECDSA).unwrap();
+        assert_eq!(key.key_type(), "ecdsa-sha2-nistp256");
+        assert_eq!(key.comment(), None);
+        assert_eq!(key.blob().len(), 104);
+    }
+
+    #[test]
+    fn test_authorized_keys_options() {
+        let output = include_str!("../tests/fixtures/ssh/authorized_keys.bubblebabble");
+        let fingerprints = ssh_keygen(output);
+        assert_eq!(fingerprints.len(), 3);
+        for fingerprint in fingerprints {
+            let key = find_key(AUTHORIZED_KEYS, fingerprint).unwrap();
+            assert!(key.verify(fingerprint));
+        }
+        let key = PublicKey::parse(AUTHORIZED_KEYS.lines().nth(2).unwrap()).unwrap();
+        assert_eq!(key, PublicKey::parse(ED25519).unwrap());
+    }
+
+    #[test]
+    fn test_verify() {
+        let key = PublicKey::parse(ED25519).unwrap();
+        let fingerprint = "xufev-lyhyl-hikys-lahur-gylop-huhit-zonal-nuzut-lymyf-nycob-ruxex";
+        assert!(key.verify(fingerprint));
+        assert!(key.verify(" XUFEV-lyhyl-hikys-lahur-gylop-huhit-zonal-nuzut-lymyf-nycob-ruxex\n"));
+        assert!(!key.verify("xufev-lyhyl-hikys-lahur-gylop-huhit-zonal-nuzut-lymyf-nycob-rux"));
+        assert_eq!(find_key(RSA, fingerprint), None);
+        assert_eq!(find_key(ED25519, fingerprint), Some(key));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let ed25519 = ED25519.split_whitespace().nth(1).unwrap();
+        let cases = [
+            ("", ParseKeyError::MissingKeyType),
+            ("ssh-ed25519", ParseKeyError::MissingKey),
+            ("ssh-ed25519 not-base64", ParseKeyError::InvalidBase64),
+            ("ssh-ed25519 AAAA", ParseKeyError::MalformedKey),
+            ("ssh-ed25519 AAAAAQ==", ParseKeyError::MalformedKey),
+        ];
+        for (line, err) in cases {
+            assert_eq!(PublicKey::parse(line), Err(err), "{line}");
+        }
+        let line = alloc::format!("ssh-rsa {ed25519}");
+        assert_eq!(PublicKey::parse(&line), Err(ParseKeyError::KeyTypeMismatch));
+        let line = alloc::format!("restrict ssh-rsa {ed25519}");
+        assert_eq!(PublicKey::parse(&line), Err(ParseKeyError::KeyTypeMismatch));
+    }
+}
@@ -0,0 +1,7 @@
+use alloc::vec::Vec;
+
+/// Return `len` bytes of test data that cover every byte value and do not
+/// repeat every 256 bytes.
+pub fn data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + i / 256) as u8).collect()
+}
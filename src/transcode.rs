@@ -0,0 +1,311 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::base64::{self, ChunkEncoder};
+use crate::decode::decode_blocks;
+use crate::{DecodeError, Encoder};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Encode hex with optional separators and prefix.
+pub fn from_hex(input: &[u8]) -> Result<String, TranscodeError> {
+    let mut start = data_start(input, b":=");
+    if matches!(input.get(start..start + 2), Some(b"0x" | b"0X")) {
+        start += 2;
+    }
+    let mut encoder = Encoder::with_capacity(input.len() / 2);
+    let mut high = None;
+    for (pos, &byte) in input.iter().enumerate().skip(start) {
+        match (hex_digit(byte), high) {
+            (Some(low), Some(high_nibble)) => {
+                encoder.update([(high_nibble << 4) | low]);
+                high = None;
+            }
+            (Some(digit), None) => high = Some(digit),
+            // Separators are only allowed between bytes.
+            (None, None) if matches!(byte, b':' | b'-') || byte.is_ascii_whitespace() => {}
+            _ => return Err(TranscodeError::InvalidByte(pos)),
+        }
+    }
+    if high.is_some() {
+        return Err(TranscodeError::Truncated);
+    }
+    Ok(encoder.finish())
+}
+
+/// Encode base64 with optional padding, whitespace and prefix.
+pub fn from_base64(input: &[u8]) -> Result<String, TranscodeError> {
+    let start = data_start(input, b":");
+    let mut encoder = Encoder::with_capacity(input.len() / 4 * 3 + 2);
+    let mut group = 0_u32;
+    let mut sextets = 0;
+    let mut padding = 0;
+    for (pos, &byte) in input.iter().enumerate().skip(start) {
+        if byte.is_ascii_whitespace() {
+            continue;
+        }
+        match base64::sextet(byte) {
+            Some(value) if padding == 0 => {
+                group = (group << 6) | u32::from(value);
+                sextets += 1;
+                if sextets == 4 {
+                    let [_, bytes @ ..] = group.to_be_bytes();
+                    encoder.update(bytes);
+                    group = 0;
+                    sextets = 0;
+                }
+            }
+            // Padding completes a group of 2 or 3 sextets.
+            None if byte == b'=' && sextets >= 2 && sextets + padding < 4 => padding += 1,
+            _ => return Err(TranscodeError::InvalidByte(pos)),
+        }
+    }
+    if sextets == 1 || (padding > 0 && sextets + padding < 4) {
+        return Err(TranscodeError::Truncated);
+    }
+    if sextets > 0 {
+        let bytes = (group << (6 * (4 - sextets))).to_be_bytes();
+        encoder.update(&bytes[1..sextets]);
+    }
+    Ok(encoder.finish())
+}
+
+/// Decode `encoded` to lowercase hex.
+pub fn to_hex(encoded: &[u8]) -> Result<String, DecodeError> {
+    let mut hex = String::with_capacity(2 * crate::max_decoded_len(encoded.len()));
+    decode_blocks(encoded, |decoded| {
+        for &byte in decoded {
+            hex.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+            hex.push(char::from(HEX_DIGITS[usize::from(byte & 15)]));
+        }
+    })?;
+    Ok(hex)
+}
+
+/// Decode `encoded` to standard base64 with padding.
+pub fn to_base64(encoded: &[u8]) -> Result<String, DecodeError> {
+    let len = crate::max_decoded_len(encoded.len());
+    let mut out = String::with_capacity(len.div_ceil(3) * 4);
+    let mut base64 = ChunkEncoder::default();
+    decode_blocks(encoded, |decoded| base64.update(decoded, &mut out))?;
+    base64.finish(&mut out);
+    Ok(out)
+}
+
+/// Return the position of the data in `input` after leading whitespace and a
+/// label like `SHA256:` or `SHA256 Fingerprint=` that ends in one of
+/// `delimiters`.
+///
+/// A label starts with a letter, has only ASCII letters, digits, spaces, `-`
+/// and `_`, and has a letter that is not a hex digit, so that hex with mixed
+/// separators like `ab cd:ef` is not mistaken for one.
+fn data_start(input: &[u8], delimiters: &[u8]) -> usize {
+    let start = input
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(input.len());
+    let rest = &input[start..];
+    let Some(end) = rest.iter().position(|byte| delimiters.contains(byte)) else {
+        return start;
+    };
+    let label = &rest[..end];
+    let is_label = label.first().is_some_and(u8::is_ascii_alphabetic)
+        && label
+            .split(|byte| b" -_".contains(byte))
+            .any(|token| !token.iter().all(u8::is_ascii_hexdigit))
+        && label
+            .iter()
+            .all(|&byte| byte.is_ascii_alphanumeric() || matches!(byte, b' ' | b'-' | b'_'));
+    if is_label {
+        start + end + 1
+    } else {
+        start
+    }
+}
+
+const fn hex_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Errors from [`boba::from_hex`](crate::from_hex()) and
+/// [`boba::from_base64`](crate::from_base64()).
+///
+/// # Examples
+///
+/// ```
+/// # use boba::TranscodeError;
+/// assert_eq!(boba::from_hex("SHA256:ab:cg"), Err(TranscodeError::InvalidByte(11)));
+/// assert_eq!(boba::from_hex("ab:c"), Err(TranscodeError::Truncated));
+/// assert_eq!(boba::from_base64("Zm9v!"), Err(TranscodeError::InvalidByte(4)));
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TranscodeError {
+    /// The byte at the given position in the input is not a digit, padding
+    /// or separator where one is allowed.
+    InvalidByte(usize),
+    /// The input ends in the middle of a byte or base64 group.
+    Truncated,
+}
+
+impl core::error::Error for TranscodeError {}
+
+impl fmt::Display for TranscodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidByte(pos) => write!(f, "Encountered invalid byte at position {pos}"),
+            Self::Truncated => f.write_str("Input ends in the middle of a byte"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::BLOCK_WORDS;
+    use crate::testing::data;
+
+    // `ssh-keygen -l -E md5` and `ssh-keygen -l` print these fingerprints of
+    // tests/fixtures/ssh/ed25519.pub and tests/fixtures/ssh/rsa.pub.
+    const MD5_HEX: &str = "MD5:1b:0b:38:f9:2d:6c:84:00:87:f7:8d:09:b7:2e:4b:c2";
+    const SHA256_BASE64: &str = "SHA256:cYFieNPlbv72doItVAw4hs/CHmkX4sykCrl5+LN0QQM";
+
+    #[test]
+    fn test_from_hex() {
+        let encoded = crate::encode("Pineapple");
+        let cases = [
+            "50696e656170706c65",
+            "50696E656170706C65",
+            "0x50696e656170706c65",
+            "50:69:6e:65:61:70:70:6c:65",
+            "50-69-6E-65-61-70-70-6C-65",
+            "50 69 6e 65\n61 70 70 6c 65\n",
+            "  SHA1 Fingerprint=50:69:6E:65:61:70:70:6C:65",
+            "MD5:50:69:6e:65:61:70:70:6c:65",
+        ];
+        for hex in cases {
+            assert_eq!(from_hex(hex.as_bytes()).unwrap(), encoded, "{hex}");
+        }
+        assert_eq!(from_hex(b"").unwrap(), "xexax");
+        assert_eq!(
+            from_hex(b"dead:beef").unwrap(),
+            crate::encode([0xde, 0xad, 0xbe, 0xef])
+        );
+        // Hex with mixed separators is not a label.
+        let encoded = crate::encode([0xab, 0xcd, 0xef]);
+        assert_eq!(from_hex(b"ab cd:ef").unwrap(), encoded);
+        assert_eq!(from_hex(b"ab-cd:ef").unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_from_hex_errors() {
+        assert_eq!(from_hex(b"ab:cg"), Err(TranscodeError::InvalidByte(4)));
+        assert_eq!(from_hex(b"ab:c:de"), Err(TranscodeError::InvalidByte(4)));
+        assert_eq!(from_hex(b"MD5:ab:c"), Err(TranscodeError::Truncated));
+        assert_eq!(from_hex(b"MD5:ab:c "), Err(TranscodeError::InvalidByte(8)));
+        assert_eq!(from_hex(b"MD5 ab"), Err(TranscodeError::InvalidByte(0)));
+        assert_eq!(from_hex(b"0x0x12"), Err(TranscodeError::InvalidByte(3)));
+        assert_eq!(from_hex(b"*abc:12"), Err(TranscodeError::InvalidByte(0)));
+        assert_eq!(from_hex(b"a1 b2=c3"), Err(TranscodeError::InvalidByte(5)));
+        assert_eq!(from_hex(b"AB_CD:EF"), Err(TranscodeError::InvalidByte(2)));
+    }
+
+    #[test]
+    fn test_from_base64() {
+        let encoded = crate::encode("Pineapple pizza");
+        let cases = [
+            "UGluZWFwcGxlIHBpenph",
+            "UGluZWFw\ncGxlIHBpenph\n",
+            "SHA256:UGluZWFwcGxlIHBpenph",
+        ];
+        for base64 in cases {
+            assert_eq!(from_base64(base64.as_bytes()).unwrap(), encoded, "{base64}");
+        }
+        let cases: [(&str, &[u8]); 5] = [
+            ("", b""),
+            ("Zg==", b"f"),
+            ("Zg", b"f"),
+            ("Zm8=", b"fo"),
+            ("Zm9vYg", b"foob"),
+        ];
+        for (base64, data) in cases {
+            assert_eq!(from_base64(base64.as_bytes()).unwrap(), crate::encode(data));
+        }
+    }
+
+    #[test]
+    fn test_from_base64_errors() {
+        assert_eq!(
+            from_base64(b"Zm9v!mFy"),
+            Err(TranscodeError::InvalidByte(4))
+        );
+        assert_eq!(
+            from_base64(b"SHA256:Zm9v!mFy"),
+            Err(TranscodeError::InvalidByte(11))
+        );
+        assert_eq!(from_base64(b"Zm=v"), Err(TranscodeError::InvalidByte(3)));
+        assert_eq!(from_base64(b"Zm9v="), Err(TranscodeError::InvalidByte(4)));
+        assert_eq!(from_base64(b"Zg==="), Err(TranscodeError::InvalidByte(4)));
+        assert_eq!(from_base64(b"Zm9vY"), Err(TranscodeError::Truncated));
+        assert_eq!(from_base64(b"Zg="), Err(TranscodeError::Truncated));
+        assert_eq!(
+            from_base64(b"ab:cd:ef"),
+            Err(TranscodeError::InvalidByte(2))
+        );
+    }
+
+    #[test]
+    fn test_openssh_fingerprints() {
+        let md5 = from_hex(MD5_HEX.as_bytes()).unwrap();
+        assert_eq!(md5, "xekob-revuz-nurik-secab-bocyz-lyfab-nytud-vydos-dexox");
+        assert_eq!(
+            to_hex(md5.as_bytes()).unwrap(),
+            MD5_HEX[4..].replace(':', "")
+        );
+
+        let sha256 = from_base64(SHA256_BASE64.as_bytes()).unwrap();
+        assert_eq!(
+            sha256,
+            "xisem-cimal-mugiv-hiraz-vatel-kobad-tehab-savum-kefis-deluk-nihav-dufop-geder-novoz-mosyl-gebab-fixyx"
+        );
+        // OpenSSH strips the padding.
+        let base64 = to_base64(sha256.as_bytes()).unwrap();
+        assert_eq!(base64, [&SHA256_BASE64[7..], "="].concat());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = data(6 * BLOCK_WORDS + 3);
+        for len in [0, 1, 2, 3, 2 * BLOCK_WORDS + 1, data.len()] {
+            let encoded = crate::encode(&data[..len]);
+            let hex = to_hex(encoded.as_bytes()).unwrap();
+            assert_eq!(hex.len(), 2 * len);
+            assert_eq!(from_hex(hex.as_bytes()).unwrap(), encoded);
+            let base64 = to_base64(encoded.as_bytes()).unwrap();
+            let mut expected = String::new();
+            base64::encode(&data[..len], &mut expected).unwrap();
+            assert_eq!(base64, expected);
+            assert_eq!(from_base64(base64.as_bytes()).unwrap(), encoded);
+        }
+    }
+
+    #[test]
+    fn test_to_errors_match_decode() {
+        let cases = [
+            "xy",
+            "yx",
+            "xigak-nyryk-humil-bosek-sonox",
+            "xig!k-nyryk-sonax",
+        ];
+        for encoded in cases {
+            let err = crate::decode(encoded).unwrap_err();
+            assert_eq!(to_hex(encoded.as_bytes()), Err(err));
+            assert_eq!(to_base64(encoded.as_bytes()), Err(err));
+        }
+    }
+}
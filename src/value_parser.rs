@@ -0,0 +1,256 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use std::ffi::OsStr;
+
+use clap::builder::{TypedValueParser, ValueHint};
+use clap::{Arg, Command};
+
+use crate::partial::Machine;
+use crate::DecodeError;
+
+/// A [`clap`] value parser for Bubble Babble-encoded arguments.
+///
+/// Arguments are decoded to a [`Vec<u8>`](Vec). Invalid arguments are
+/// reported in clap's error style with the [`DecodeError`] and a marker
+/// pointing at the first character that cannot be part of a valid encoding.
+///
+/// Use [`configure`](Self::configure) to also hint the expected format in
+/// help output and shell completions.
+///
+/// [`clap`]: https://docs.rs/clap
+///
+/// # Examples
+///
+/// ```
+/// use boba::BubbleBabbleValueParser;
+/// use clap::{Arg, Command};
+///
+/// let cmd = Command::new("verify").arg(
+///     BubbleBabbleValueParser::new()
+///         .exact_len(9)
+///         .lenient(true)
+///         .configure(Arg::new("fingerprint")),
+/// );
+///
+/// let matches = cmd
+///     .clone()
+///     .try_get_matches_from(["verify", " XIGAK-nyryk-humil-bosek-sonax"])
+///     .unwrap();
+/// let fingerprint = matches.get_one::<Vec<u8>>("fingerprint").unwrap();
+/// assert_eq!(fingerprint, b"Pineapple");
+///
+/// let err = cmd
+///     .try_get_matches_from(["verify", "xesef-disof-gytuf-katof-movif-baxux"])
+///     .unwrap_err();
+/// assert!(err.to_string().contains("expected 9 bytes, got 10"));
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BubbleBabbleValueParser {
+    len: Option<usize>,
+    lenient: bool,
+}
+
+impl BubbleBabbleValueParser {
+    /// Create a parser that accepts strict encodings of any length.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            len: None,
+            lenient: false,
+        }
+    }
+
+    /// Only accept encodings of exactly `len` bytes, like the digests of a
+    /// known hash function.
+    #[must_use]
+    pub const fn exact_len(mut self, len: usize) -> Self {
+        self.len = Some(len);
+        self
+    }
+
+    /// Accept encodings with surrounding whitespace and uppercase letters,
+    /// like fingerprints copied from documents or read aloud.
+    #[must_use]
+    pub const fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Configure `arg` to use this parser and hint the expected format.
+    ///
+    /// The value name shown in help output is `BUBBLEBABBLE`, and shells are
+    /// told not to complete file paths for the argument.
+    #[must_use]
+    pub fn configure(self, arg: Arg) -> Arg {
+        arg.value_parser(self)
+            .value_name("BUBBLEBABBLE")
+            .value_hint(ValueHint::Other)
+    }
+
+    fn parse(self, value: &str) -> Result<Vec<u8>, ArgumentError> {
+        let normalized;
+        let value = if self.lenient {
+            normalized = value.trim().to_ascii_lowercase();
+            normalized.as_str()
+        } else {
+            value
+        };
+        let data = crate::decode(value).map_err(|err| ArgumentError::decode(value, err))?;
+        match self.len {
+            Some(len) if data.len() != len => Err(ArgumentError::Length {
+                expected: len,
+                actual: data.len(),
+            }),
+            _ => Ok(data),
+        }
+    }
+}
+
+impl TypedValueParser for BubbleBabbleValueParser {
+    type Value = Vec<u8>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        // Reuse clap's UTF-8 and value validation errors.
+        let parser = *self;
+        let parse = move |value: &str| parser.parse(value);
+        parse.parse_ref(cmd, arg, value)
+    }
+}
+
+/// The source of the clap errors reported by [`BubbleBabbleValueParser`].
+#[derive(Debug)]
+enum ArgumentError {
+    Decode {
+        err: DecodeError,
+        value: String,
+        // The character position of the first rejected character.
+        column: usize,
+    },
+    Length {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl ArgumentError {
+    fn decode(value: &str, err: DecodeError) -> Self {
+        // Find the first byte that cannot extend the input to a valid
+        // encoding. Every byte before it is ASCII.
+        let mut machine = Machine::new();
+        let pos = value
+            .bytes()
+            .position(|byte| machine.push(byte).is_err())
+            .unwrap_or(value.len());
+        Self::Decode {
+            err,
+            value: value.to_string(),
+            column: value[..pos].chars().count(),
+        }
+    }
+}
+
+impl fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode { err, value, column } => {
+                write!(f, "{err}\n\n  {value}\n  {:column$}^", "")
+            }
+            Self::Length { expected, actual } => {
+                write!(f, "expected {expected} bytes, got {actual}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ArgumentError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Decode { err, .. } => Some(err),
+            Self::Length { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    use clap::error::ErrorKind;
+    use clap::{Arg, Command};
+
+    use super::*;
+
+    fn command(parser: BubbleBabbleValueParser) -> Command {
+        Command::new("test").arg(parser.configure(Arg::new("key").long("key")))
+    }
+
+    fn parse(parser: BubbleBabbleValueParser, value: &str) -> Result<Vec<u8>, clap::Error> {
+        let matches = command(parser).try_get_matches_from(["test", "--key", value])?;
+        Ok(matches.get_one::<Vec<u8>>("key").unwrap().clone())
+    }
+
+    #[test]
+    fn test_parse() {
+        let parser = BubbleBabbleValueParser::new();
+        assert_eq!(parse(parser, "xexax").unwrap(), b"");
+        assert_eq!(
+            parse(parser, "xigak-nyryk-humil-bosek-sonax").unwrap(),
+            b"Pineapple"
+        );
+        assert_eq!(
+            parse(parser.exact_len(9), "xigak-nyryk-humil-bosek-sonax").unwrap(),
+            b"Pineapple"
+        );
+    }
+
+    #[test]
+    fn test_lenient() {
+        let value = "  Xigak-NYRYK-humil-bosek-sonax\n";
+        let parser = BubbleBabbleValueParser::new();
+        assert!(parse(parser, value).is_err());
+        assert_eq!(parse(parser.lenient(true), value).unwrap(), b"Pineapple");
+    }
+
+    #[test]
+    fn test_errors_point_at_bad_character() {
+        let parser = BubbleBabbleValueParser::new();
+        let err = parse(parser, "xigak-nyr!k-humil-bosek-sonax").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let message = err.to_string();
+        assert!(message.contains("invalid value 'xigak-nyr!k-humil-bosek-sonax'"));
+        assert!(message.contains("at position 9"));
+        assert!(message.contains("  xigak-nyr!k-humil-bosek-sonax\n           ^"));
+
+        let err = parse(parser, "xesef-disof-gytuf-katof-movif-baxix").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Checksum mismatch"));
+        // The checksum vowel is rejected.
+        assert!(message.contains(&format!("\n{}^\n", " ".repeat(2 + 33))));
+
+        let err = parse(parser, "xigak").unwrap_err();
+        assert!(err.to_string().contains("  xigak\n       ^"));
+    }
+
+    #[test]
+    fn test_length_error() {
+        let parser = BubbleBabbleValueParser::new().exact_len(8);
+        let err = parse(parser, "xigak-nyryk-humil-bosek-sonax").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err.to_string().contains("expected 8 bytes, got 9"));
+    }
+
+    #[test]
+    fn test_configure_hints_format() {
+        let cmd = command(BubbleBabbleValueParser::new());
+        let arg = cmd.get_arguments().next().unwrap();
+        assert_eq!(arg.get_value_hint(), ValueHint::Other);
+        assert_eq!(arg.get_value_names().unwrap()[0].as_str(), "BUBBLEBABBLE");
+    }
+}
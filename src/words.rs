@@ -0,0 +1,444 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::decode::{
+    decode_2_tuple, decode_3_tuple, decode_tail, index_from_consonant, index_from_vowel,
+    strip_frame,
+};
+use crate::{Checksum, DecodeError};
+
+/// A single lexical element of a Bubble Babble encoding.
+///
+/// Tokens are yielded by [`Words`] in input order and their spans partition
+/// the encoded input: every byte belongs to exactly one token.
+///
+/// See [`boba::words`](crate::words()) for more details.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Token {
+    /// The leading `x` header.
+    Header(Range<usize>),
+    /// A pronounceable word, see [`Word`].
+    Word(Word),
+    /// A `-` separating two words.
+    Separator(Range<usize>),
+    /// The final `x` trailer.
+    Trailer(Range<usize>),
+}
+
+impl Token {
+    /// Return the byte span of this token in the encoded input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use boba::{DecodeError, Token};
+    /// # fn example() -> Result<(), DecodeError> {
+    /// let mut tokens = boba::words("xexax");
+    /// assert_eq!(tokens.next().transpose()?.map(|t| t.span()), Some(0..1));
+    /// assert_eq!(tokens.next().transpose()?.map(|t| t.span()), Some(1..4));
+    /// assert_eq!(tokens.next().transpose()?.map(|t| t.span()), Some(4..5));
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Self::Header(span) | Self::Separator(span) | Self::Trailer(span) => span.clone(),
+            Self::Word(word) => word.span(),
+        }
+    }
+
+    /// Return the contained [`Word`] if this token is a word.
+    #[must_use]
+    pub fn as_word(&self) -> Option<&Word> {
+        if let Self::Word(word) = self {
+            Some(word)
+        } else {
+            None
+        }
+    }
+}
+
+/// A pronounceable word in a Bubble Babble encoding.
+///
+/// Words are the dash-separated groups of letters in an encoding. Each word
+/// contains exactly one vowel-consonant-vowel triple, which is mixed with the
+/// running checksum, and up to two consonants that each carry half of a
+/// neighboring byte. The first word is preceded by the [header] and the last
+/// word is followed by the [trailer]; neither `x` is part of the word's span.
+///
+/// [header]: Token::Header
+/// [trailer]: Token::Trailer
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Word {
+    index: usize,
+    span: Range<usize>,
+    bytes: Range<usize>,
+    checksum: Checksum,
+    is_final: bool,
+    is_checksum: bool,
+}
+
+impl Word {
+    /// Return the zero-based position of this word in the encoding.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Return the byte span of this word in the encoded input.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Return the range of decoded bytes this word carries data for.
+    ///
+    /// Because words are offset from byte pairs by one consonant, the ranges
+    /// of adjacent words overlap: a word may only carry the low or high four
+    /// bits of the first and last bytes in its range.
+    ///
+    /// The final word of an encoding of an even number of bytes is a checksum
+    /// word. It may carry the low bits of the last byte of the input, but
+    /// otherwise encodes no data.
+    #[must_use]
+    pub fn bytes(&self) -> Range<usize> {
+        self.bytes.clone()
+    }
+
+    /// Return the running checksum mixed into the vowels of this word.
+    ///
+    /// This is the checksum state after the pairs of bytes encoded by all
+    /// preceding words.
+    #[must_use]
+    pub fn checksum(&self) -> Checksum {
+        self.checksum
+    }
+
+    /// Return whether this is the last word of the encoding.
+    #[must_use]
+    pub fn is_final(&self) -> bool {
+        self.is_final
+    }
+
+    /// Return whether this word is the final checksum word of an encoding of
+    /// an even number of bytes.
+    ///
+    /// Checksum words have an `x` in their middle position and encode the
+    /// final checksum directly in their vowels.
+    #[must_use]
+    pub fn is_checksum(&self) -> bool {
+        self.is_checksum
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Header,
+    Word,
+    Separator,
+    Trailer,
+    Done,
+}
+
+/// An iterator over the [`Token`]s of a Bubble Babble encoding.
+///
+/// This struct is created by [`boba::words`](crate::words()). See its
+/// documentation for more.
+///
+/// The iterator validates the encoding as it goes, including the embedded
+/// checksum, and reports the same errors as the decoder. Unlike the decoder,
+/// it rejects every word that is missing its `-` separator. Once an error is
+/// yielded, the iterator is exhausted.
+#[derive(Debug, Clone)]
+pub struct Words<'a> {
+    encoded: &'a [u8],
+    pos: usize,
+    index: usize,
+    checksum: Checksum,
+    state: State,
+}
+
+impl<'a> Words<'a> {
+    pub(crate) fn new(encoded: &'a [u8]) -> Self {
+        Self {
+            encoded,
+            pos: 0,
+            index: 0,
+            checksum: Checksum::initial(),
+            state: State::Header,
+        }
+    }
+
+    fn next_word(&mut self) -> Result<Word, DecodeError> {
+        let start = self.pos;
+        let index = self.index;
+        let checksum = self.checksum;
+        // Every word after the first leads with the trailing consonant of the
+        // previous tuple.
+        let tuple = if index == 0 { start } else { start + 1 };
+        let trailer = self.encoded.len() - 1;
+        let bytes_start = index.saturating_mul(2).saturating_sub(1);
+
+        // Panic safety:
+        //
+        // - `strip_frame` guarantees the input has at least a header and a
+        //   trailer, so `trailer >= 1`.
+        // - A word only starts after a full 6 byte chunk was consumed before
+        //   the trailer, so `tuple <= trailer`.
+        match self.encoded[tuple..trailer] {
+            [left, mid, right, up, sep, down, ..] => {
+                if sep != b'-' {
+                    return Err(self.missing_separator_error());
+                }
+                let byte1 = decode_3_tuple(
+                    index_from_vowel(left).ok_or(DecodeError::ExpectedVowel)?,
+                    index_from_consonant(mid).ok_or(DecodeError::ExpectedConsonant)?,
+                    index_from_vowel(right).ok_or(DecodeError::ExpectedVowel)?,
+                    checksum.value(),
+                )?;
+                let byte2 = decode_2_tuple(
+                    index_from_consonant(up).ok_or(DecodeError::ExpectedConsonant)?,
+                    index_from_consonant(down).ok_or(DecodeError::ExpectedConsonant)?,
+                );
+                self.checksum = checksum.update([byte1, byte2]);
+                self.index += 1;
+                self.pos = tuple + 4;
+                self.state = State::Separator;
+                Ok(Word {
+                    index,
+                    span: start..self.pos,
+                    bytes: bytes_start..2 * index + 2,
+                    checksum,
+                    is_final: false,
+                    is_checksum: false,
+                })
+            }
+            [left, mid, right] => {
+                let is_checksum = decode_tail([left, mid, right], checksum)?.is_none();
+                self.index += 1;
+                self.pos = trailer;
+                self.state = State::Trailer;
+                let bytes_end = if is_checksum {
+                    2 * index
+                } else {
+                    2 * index + 1
+                };
+                Ok(Word {
+                    index,
+                    span: start..trailer,
+                    bytes: bytes_start..bytes_end,
+                    checksum,
+                    is_final: true,
+                    is_checksum,
+                })
+            }
+            _ => Err(DecodeError::Corrupted),
+        }
+    }
+
+    /// Return the error for a word that is missing its `-` separator.
+    ///
+    /// The decoder stops reading words at such a word and decodes the final
+    /// 3-tuple of the input with the current checksum. Report the error it
+    /// finds there, or [`DecodeError::Corrupted`] where it finds none.
+    fn missing_separator_error(&self) -> DecodeError {
+        let enc = &self.encoded[1..self.encoded.len() - 1];
+        let &[left, mid, right] = &enc[enc.len() - enc.len() % 6..] else {
+            return DecodeError::Corrupted;
+        };
+        decode_tail([left, mid, right], self.checksum)
+            .err()
+            .unwrap_or(DecodeError::Corrupted)
+    }
+}
+
+impl Iterator for Words<'_> {
+    type Item = Result<Token, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.state {
+            State::Header => {
+                if let Err(err) = strip_frame(self.encoded) {
+                    self.state = State::Done;
+                    return Some(Err(err));
+                }
+                self.pos = 1;
+                self.state = State::Word;
+                Some(Ok(Token::Header(0..1)))
+            }
+            State::Word => match self.next_word() {
+                Ok(word) => Some(Ok(Token::Word(word))),
+                Err(err) => {
+                    self.state = State::Done;
+                    Some(Err(err))
+                }
+            },
+            State::Separator => {
+                let span = self.pos..self.pos + 1;
+                self.pos += 1;
+                self.state = State::Word;
+                Some(Ok(Token::Separator(span)))
+            }
+            State::Trailer => {
+                let span = self.pos..self.pos + 1;
+                self.pos += 1;
+                self.state = State::Done;
+                Some(Ok(Token::Trailer(span)))
+            }
+            State::Done => None,
+        }
+    }
+}
+
+impl FusedIterator for Words<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn tokens(encoded: &str) -> Result<Vec<Token>, DecodeError> {
+        Words::new(encoded.as_bytes()).collect()
+    }
+
+    fn word_spans(encoded: &str) -> Vec<&str> {
+        Words::new(encoded.as_bytes())
+            .filter_map(|token| token.unwrap().as_word().map(Word::span))
+            .map(|span| &encoded[span])
+            .collect()
+    }
+
+    #[test]
+    fn test_words_empty() {
+        let tokens = tokens("xexax").unwrap();
+        assert_eq!(
+            tokens,
+            [
+                Token::Header(0..1),
+                Token::Word(Word {
+                    index: 0,
+                    span: 1..4,
+                    bytes: 0..0,
+                    checksum: Checksum::initial(),
+                    is_final: true,
+                    is_checksum: true,
+                }),
+                Token::Trailer(4..5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_words_odd_length() {
+        let tokens = tokens("xigak-nyryk-humil-bosek-sonax").unwrap();
+        assert_eq!(tokens.len(), 11);
+        assert_eq!(tokens[0], Token::Header(0..1));
+        assert_eq!(tokens[2], Token::Separator(5..6));
+        assert_eq!(tokens[10], Token::Trailer(28..29));
+
+        let words = tokens.iter().filter_map(Token::as_word).collect::<Vec<_>>();
+        let bytes = words.iter().map(|word| word.bytes()).collect::<Vec<_>>();
+        assert_eq!(bytes, [0..2, 1..4, 3..6, 5..8, 7..9]);
+        assert!(words.iter().all(|word| !word.is_checksum()));
+        assert!(words[4].is_final());
+        assert!(!words[3].is_final());
+
+        assert_eq!(
+            word_spans("xigak-nyryk-humil-bosek-sonax"),
+            ["igak", "nyryk", "humil", "bosek", "sona"]
+        );
+    }
+
+    #[test]
+    fn test_words_even_length() {
+        let encoded = "xesef-disof-gytuf-katof-movif-baxux";
+        let tokens = tokens(encoded).unwrap();
+        let last = tokens
+            .iter()
+            .filter_map(Token::as_word)
+            .next_back()
+            .unwrap();
+        assert!(last.is_final());
+        assert!(last.is_checksum());
+        assert_eq!(last.index(), 5);
+        assert_eq!(last.bytes(), 9..10);
+        assert_eq!(&encoded[last.span()], "baxu");
+    }
+
+    #[test]
+    fn test_words_spans_partition_input() {
+        for encoded in [
+            "xexax",
+            "xebax",
+            "xebab-byxax",
+            "xuzob-bibyx",
+            "xesef-disof-gytuf-katof-movif-baxux",
+            "xusan-zugom-vesin-zenom-bumun-tanav-zyvam-zomon-sapaz-bulin-dypux",
+        ] {
+            let mut next = 0;
+            for token in Words::new(encoded.as_bytes()) {
+                let span = token.unwrap().span();
+                assert_eq!(span.start, next);
+                next = span.end;
+            }
+            assert_eq!(next, encoded.len());
+        }
+    }
+
+    #[test]
+    fn test_words_checksum_matches_recurrence() {
+        let checksums = Words::new(b"xesef-disof-gytuf-katof-movif-baxux")
+            .filter_map(|token| token.unwrap().as_word().map(Word::checksum))
+            .map(Checksum::value)
+            .collect::<Vec<_>>();
+        let mut expected = Vec::new();
+        let mut checksum = 1_u16;
+        expected.push(checksum as u8);
+        for pair in b"1234567890".chunks_exact(2) {
+            checksum = (checksum * 5 + u16::from(pair[0]) * 7 + u16::from(pair[1])) % 36;
+            expected.push(checksum as u8);
+        }
+        assert_eq!(checksums, expected);
+    }
+
+    #[test]
+    fn test_words_errors() {
+        assert_eq!(tokens(""), Err(DecodeError::Corrupted));
+        assert_eq!(tokens("yx"), Err(DecodeError::MalformedHeader));
+        assert_eq!(tokens("xy"), Err(DecodeError::MalformedTrailer));
+        assert_eq!(tokens("xx"), Err(DecodeError::Corrupted));
+        assert_eq!(tokens("x789x"), Err(DecodeError::InvalidByte(1)));
+        assert_eq!(
+            tokens("xssef-disof-gytuf-katof-movif-baxux"),
+            Err(DecodeError::ExpectedVowel)
+        );
+        assert_eq!(
+            tokens("xeeef-disof-gytuf-katof-movif-baxux"),
+            Err(DecodeError::ExpectedConsonant)
+        );
+        // A word missing its separator reports the error of the final tuple
+        // like the decoder, or `Corrupted` where the decoder accepts it.
+        assert_eq!(
+            tokens("xesefxdisofxgytufxkatofxmovifxbaxux"),
+            Err(DecodeError::ChecksumMismatch)
+        );
+        assert_eq!(tokens("xebabxbexax"), Err(DecodeError::Corrupted));
+        assert_eq!(
+            tokens("xesef-disof-gytuf-katof-movif-baxax"),
+            Err(DecodeError::ChecksumMismatch)
+        );
+        assert_eq!(tokens("xabab-bx"), Err(DecodeError::Corrupted));
+    }
+
+    #[test]
+    fn test_words_yields_tokens_before_error() {
+        let mut words = Words::new(b"xesef-disof-gytuf-katof-movif-baxax");
+        let valid = words.by_ref().take_while(Result::is_ok).count();
+        // header, 5 words and 5 separators precede the bad checksum word
+        assert_eq!(valid, 11);
+        assert_eq!(words.next(), None);
+    }
+}
@@ -0,0 +1,354 @@
+//! Bubble Babble fingerprints of X.509 certificates.
+//!
+//! Certificates are pinned by the hash of the whole certificate, which is
+//! what `openssl x509 -fingerprint` prints in hex, or by the hash of its
+//! `SubjectPublicKeyInfo`, which stays the same when a certificate is renewed
+//! with the same key. This module parses PEM and DER certificates just
+//! enough to find both, and returns their hashes in the Bubble Babble
+//! encoding so they can be read aloud and compared.
+//!
+//! # Examples
+//!
+//! ```
+//! use boba::fingerprint::Algorithm;
+//! use boba::x509::Certificate;
+//!
+//! # fn example() -> Result<(), boba::x509::ParseCertificateError> {
+//! let pem = "\
+//! -----BEGIN CERTIFICATE-----
+//! MIIBgzCCASmgAwIBAgIUNNmTv36Q0ZvDhPJJ6I8Ye73q4VIwCgYIKoZIzj0EAwIw
+//! FzEVMBMGA1UEAwwMYm9iYS5leGFtcGxlMB4XDTI2MTAxODIxNDYxNVoXDTM2MTAx
+//! NTIxNDYxNVowFzEVMBMGA1UEAwwMYm9iYS5leGFtcGxlMFkwEwYHKoZIzj0CAQYI
+//! KoZIzj0DAQcDQgAEnq0iKTNPYE/a03d0sP7Q+kY0qPTDsAH0A8co+y/OmU3Fqh0v
+//! UkXoNHLhmVRlvT/WDfaMm0eTKJI0X/WBDWjci6NTMFEwHQYDVR0OBBYEFFk5ZnWU
+//! JYLbF7tAhbopiE9s7DOhMB8GA1UdIwQYMBaAFFk5ZnWUJYLbF7tAhbopiE9s7DOh
+//! MA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIgT+5CWhD0nWPtGQS4
+//! /U6Bqci4vUIkKD6Ee1Iz5wQT8Q4CIQC1Z6+7UR7JSkN4gNt5QfGU5kEdWEsCZ4pj
+//! C8pta5l3Ug==
+//! -----END CERTIFICATE-----
+//! ";
+//! let cert = Certificate::parse(pem)?;
+//! // `openssl x509 -fingerprint -sha256` prints
+//! // 2F:2E:6A:6D:05:7F:CE:16:CB:EB:60:A8:A6:EF:48:32:
+//! // 61:49:11:00:25:3F:3A:EE:71:4F:8F:C9:A5:C6:46:26
+//! assert_eq!(
+//!     cert.fingerprint(Algorithm::Sha256),
+//!     "xerod-vopyk-tocol-zafyc-kadav-rumyp-mynev-zedof-domag-nagob-bynaf-zyvav-vusag-zyfes-nenes-kycod-kixax"
+//! );
+//! assert_eq!(
+//!     cert.spki_fingerprint(Algorithm::Sha256),
+//!     "xelon-nofum-luzun-kolec-bimit-gobeg-goreh-baluk-fupus-ginob-dapot-nulos-cucah-lypip-sutef-gocen-kexex"
+//! );
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::base64;
+use crate::fingerprint::Algorithm;
+
+const SEQUENCE: u8 = 0x30;
+const INTEGER: u8 = 0x02;
+// The context-specific, constructed tag of the explicit version field.
+const VERSION: u8 = 0xa0;
+
+const PEM_BEGIN: &[u8] = b"-----BEGIN CERTIFICATE-----";
+const PEM_END: &[u8] = b"-----END CERTIFICATE-----";
+
+/// An X.509 certificate in DER form.
+///
+/// Only the structure of the certificate up to its `SubjectPublicKeyInfo` is
+/// checked. The certificate is not verified.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Certificate {
+    der: Vec<u8>,
+    // The range of the SubjectPublicKeyInfo element in `der`.
+    spki: Range<usize>,
+}
+
+impl Certificate {
+    /// Parse a PEM or DER certificate.
+    ///
+    /// Input starting with a DER `SEQUENCE` is parsed with
+    /// [`from_der`](Self::from_der), and other input with
+    /// [`from_pem`](Self::from_pem).
+    ///
+    /// # Errors
+    ///
+    /// If the input is not a PEM or DER certificate, an error is returned.
+    pub fn parse<T: AsRef<[u8]>>(input: T) -> Result<Self, ParseCertificateError> {
+        let input = input.as_ref();
+        if input.first() == Some(&SEQUENCE) {
+            Self::from_der(input)
+        } else {
+            Self::from_pem(input)
+        }
+    }
+
+    /// Parse a DER certificate.
+    ///
+    /// # Errors
+    ///
+    /// If `der` is not a DER certificate,
+    /// [`ParseCertificateError::MalformedDer`] is returned.
+    pub fn from_der<T: AsRef<[u8]>>(der: T) -> Result<Self, ParseCertificateError> {
+        let der = der.as_ref().to_vec();
+        let spki = find_spki(&der)?;
+        Ok(Self { der, spki })
+    }
+
+    /// Parse the first `CERTIFICATE` block of a PEM file.
+    ///
+    /// Text before and after the block is ignored, like the output of
+    /// `openssl x509 -text`.
+    ///
+    /// # Errors
+    ///
+    /// If `pem` has no `CERTIFICATE` block or the block is not a DER
+    /// certificate in base64, an error is returned.
+    pub fn from_pem<T: AsRef<[u8]>>(pem: T) -> Result<Self, ParseCertificateError> {
+        let pem = pem.as_ref();
+        let start = find(pem, PEM_BEGIN).ok_or(ParseCertificateError::MissingPemBlock)?;
+        let pem = &pem[start + PEM_BEGIN.len()..];
+        let end = find(pem, PEM_END).ok_or(ParseCertificateError::MissingPemBlock)?;
+        let encoded = pem[..end]
+            .iter()
+            .copied()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect::<Vec<_>>();
+        let der = base64::decode(&encoded).map_err(|_| ParseCertificateError::InvalidBase64)?;
+        let spki = find_spki(&der)?;
+        Ok(Self { der, spki })
+    }
+
+    /// Return the DER encoding of the certificate.
+    #[must_use]
+    pub fn as_der(&self) -> &[u8] {
+        &self.der
+    }
+
+    /// Return the DER encoding of the `SubjectPublicKeyInfo` of the
+    /// certificate.
+    #[must_use]
+    pub fn spki_der(&self) -> &[u8] {
+        &self.der[self.spki.clone()]
+    }
+
+    /// Return the Bubble Babble encoding of the hash of the certificate.
+    ///
+    /// This is the fingerprint `openssl x509 -fingerprint` prints in hex.
+    #[must_use]
+    pub fn fingerprint(&self, algorithm: Algorithm) -> String {
+        algorithm.fingerprint(self.as_der())
+    }
+
+    /// Return the Bubble Babble encoding of the hash of the
+    /// `SubjectPublicKeyInfo` of the certificate.
+    ///
+    /// With [`Algorithm::Sha256`], this is the hash used for public key
+    /// pinning.
+    #[must_use]
+    pub fn spki_fingerprint(&self, algorithm: Algorithm) -> String {
+        algorithm.fingerprint(self.spki_der())
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Read the DER element at `pos` that must end by `limit` and have tag
+/// `tag`, and return the range of its contents.
+fn read_element(
+    der: &[u8],
+    pos: usize,
+    limit: usize,
+    tag: u8,
+) -> Result<Range<usize>, ParseCertificateError> {
+    let malformed = ParseCertificateError::MalformedDer(pos);
+    let header = der.get(pos..limit).ok_or(malformed)?;
+    let (len, header_len) = match *header {
+        [actual, len, ..] if actual != tag || len == 0x80 => return Err(malformed),
+        [_, len, ..] if len < 0x80 => (usize::from(len), 2),
+        [_, len, ref rest @ ..] => {
+            // The long form gives the number of length bytes that follow.
+            let count = usize::from(len & 0x7f);
+            let bytes = rest.get(..count).ok_or(malformed)?;
+            if count > size_of::<usize>() {
+                return Err(malformed);
+            }
+            let len = bytes
+                .iter()
+                .fold(0, |len, &byte| len << 8 | usize::from(byte));
+            (len, 2 + count)
+        }
+        _ => return Err(malformed),
+    };
+    let start = pos + header_len;
+    match start.checked_add(len) {
+        Some(end) if end <= limit => Ok(start..end),
+        _ => Err(malformed),
+    }
+}
+
+/// Return the range of the `SubjectPublicKeyInfo` element of the DER
+/// certificate `der`.
+fn find_spki(der: &[u8]) -> Result<Range<usize>, ParseCertificateError> {
+    let cert = read_element(der, 0, der.len(), SEQUENCE)?;
+    if cert.end != der.len() {
+        return Err(ParseCertificateError::MalformedDer(cert.end));
+    }
+    let tbs = read_element(der, cert.start, cert.end, SEQUENCE)?;
+    let mut pos = tbs.start;
+    if der.get(pos) == Some(&VERSION) {
+        pos = read_element(der, pos, tbs.end, VERSION)?.end;
+    }
+    // Skip the serial number, signature algorithm, issuer, validity and
+    // subject.
+    for tag in [INTEGER, SEQUENCE, SEQUENCE, SEQUENCE, SEQUENCE] {
+        pos = read_element(der, pos, tbs.end, tag)?.end;
+    }
+    let spki = read_element(der, pos, tbs.end, SEQUENCE)?;
+    Ok(pos..spki.end)
+}
+
+/// Errors from parsing an X.509 certificate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseCertificateError {
+    /// The PEM input has no `CERTIFICATE` block.
+    MissingPemBlock,
+    /// The PEM block is not valid base64.
+    InvalidBase64,
+    /// The DER encoding is not an X.509 certificate. The position of the
+    /// first unexpected DER element is included.
+    MalformedDer(usize),
+}
+
+impl core::error::Error for ParseCertificateError {}
+
+impl fmt::Display for ParseCertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPemBlock => f.write_str("Missing PEM CERTIFICATE block"),
+            Self::InvalidBase64 => f.write_str("PEM block is not valid base64"),
+            Self::MalformedDer(pos) => write!(
+                f,
+                "Encountered malformed DER certificate element at position {pos}"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+
+    const PEM: &str = include_str!("../tests/fixtures/x509/cert.pem");
+    const DER: &[u8] = include_bytes!("../tests/fixtures/x509/cert.der");
+    const FINGERPRINTS: &str = include_str!("../tests/fixtures/x509/fingerprints.txt");
+
+    /// Return the hash recorded in the `openssl` output for `name`.
+    fn recorded(name: &str) -> Vec<u8> {
+        let line = FINGERPRINTS
+            .lines()
+            .find(|line| line.starts_with(name))
+            .unwrap();
+        let hex = line.rsplit('=').next().unwrap().replace(':', "");
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_pem_and_der() {
+        let cert = Certificate::parse(PEM).unwrap();
+        assert_eq!(cert.as_der(), DER);
+        assert_eq!(Certificate::parse(DER).unwrap(), cert);
+        assert_eq!(Certificate::from_der(DER).unwrap(), cert);
+
+        let text = format!("Certificate:\n    Data: ...\n{PEM}\ntrailing text\n");
+        assert_eq!(Certificate::from_pem(text).unwrap(), cert);
+    }
+
+    #[test]
+    fn test_fingerprints_match_openssl() {
+        let cert = Certificate::parse(PEM).unwrap();
+        assert_eq!(
+            cert.fingerprint(Algorithm::Sha256),
+            crate::encode(recorded("sha256 Fingerprint"))
+        );
+        assert_eq!(
+            cert.spki_fingerprint(Algorithm::Sha256),
+            crate::encode(recorded("SPKI SHA256"))
+        );
+        #[cfg(feature = "sha1")]
+        assert_eq!(
+            cert.fingerprint(Algorithm::Sha1),
+            crate::encode(recorded("sha1 Fingerprint"))
+        );
+        #[cfg(feature = "md5")]
+        assert_eq!(
+            cert.fingerprint(Algorithm::Md5),
+            crate::encode(recorded("md5 Fingerprint"))
+        );
+    }
+
+    #[test]
+    fn test_spki() {
+        let cert = Certificate::parse(DER).unwrap();
+        let spki = cert.spki_der();
+        // An id-ecPublicKey P-256 key.
+        assert_eq!(spki[..2], [SEQUENCE, 0x59]);
+        assert_eq!(spki.len(), 0x5b);
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases: [(&[u8], ParseCertificateError); 4] = [
+            (b"", ParseCertificateError::MissingPemBlock),
+            (
+                b"-----BEGIN CERTIFICATE-----\nMIIB",
+                ParseCertificateError::MissingPemBlock,
+            ),
+            (
+                b"-----BEGIN CERTIFICATE-----\nMII!\n-----END CERTIFICATE-----",
+                ParseCertificateError::InvalidBase64,
+            ),
+            (
+                &DER[..DER.len() - 1],
+                ParseCertificateError::MalformedDer(0),
+            ),
+        ];
+        for (input, err) in cases {
+            assert_eq!(Certificate::parse(input), Err(err));
+        }
+
+        let mut trailing = DER.to_vec();
+        trailing.push(0);
+        assert_eq!(
+            Certificate::parse(&trailing),
+            Err(ParseCertificateError::MalformedDer(DER.len()))
+        );
+
+        // Replace the serial number tag, which follows the headers of the
+        // certificate and the TBSCertificate and the version field.
+        let mut der = DER.to_vec();
+        let serial = 4 + 4 + 5;
+        assert_eq!(der[serial], INTEGER);
+        der[serial] = SEQUENCE;
+        assert_eq!(
+            Certificate::parse(&der),
+            Err(ParseCertificateError::MalformedDer(serial))
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Check that the optimized encoder and decoder produce byte-identical results
+//! to the original implementation in the `reference` module, and that the
+//! tokenizer reports the same errors as the decoder.
+
+#![cfg(feature = "alloc")]
+
+mod reference;
+
+/// A small xorshift PRNG so tests are deterministic without extra
+/// dependencies.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+#[test]
+fn decode_valid_encodings_matches_reference() {
+    let mut rng = XorShift(0x5EED_BABB_1E00_0001);
+    for len in 0..256 {
+        let data = rng.bytes(len);
+        let encoded = boba::encode(&data);
+        assert_eq!(boba::decode(&encoded), Ok(data.clone()));
+        assert_eq!(reference::decode(encoded.as_bytes()), Ok(data));
+    }
+}
+
+/// Call `f` with every encoding of short data with one byte replaced.
+fn for_each_mutated_encoding(mut f: impl FnMut(&[u8])) {
+    let mut rng = XorShift(0x5EED_BABB_1E00_0002);
+    for len in 0..12 {
+        let encoded = boba::encode(rng.bytes(len)).into_bytes();
+        for pos in 0..encoded.len() {
+            for byte in 0..=u8::MAX {
+                let mut mutated = encoded.clone();
+                mutated[pos] = byte;
+                f(&mutated);
+            }
+        }
+    }
+}
+
+/// Call `f` with random strings between a header and a trailer.
+fn for_each_random_string(mut f: impl FnMut(&[u8])) {
+    // Mostly alphabet bytes with the occasional invalid byte to exercise
+    // every error path, including the precedence between them.
+    const BYTES: &[u8] = b"aeiouybcdfghklmnprstvzx-aeiouy--xxQ";
+    let mut rng = XorShift(0x5EED_BABB_1E00_0003);
+    for _ in 0..200_000 {
+        let len = (rng.next() % 40) as usize;
+        let mut input = vec![b'x'];
+        input.extend((0..len).map(|_| BYTES[(rng.next() % BYTES.len() as u64) as usize]));
+        input.push(b'x');
+        f(&input);
+    }
+}
+
+/// Call `f` with every string of up to 5 bytes of the alphabet and `Q`.
+fn for_each_short_string(mut f: impl FnMut(&[u8])) {
+    const BYTES: &[u8] = b"aeiouybcdfghklmnprstvzx-Q";
+    let mut input = Vec::new();
+    for len in 0..=5_u32 {
+        for mut n in 0..BYTES.len().pow(len) {
+            input.clear();
+            for _ in 0..len {
+                input.push(BYTES[n % BYTES.len()]);
+                n /= BYTES.len();
+            }
+            f(&input);
+        }
+    }
+}
+
+fn check_decode_matches_reference(input: &[u8]) {
+    assert_eq!(
+        boba::decode(input),
+        reference::decode(input),
+        "mismatch decoding {:?}",
+        String::from_utf8_lossy(input)
+    );
+}
+
+#[test]
+fn decode_mutated_encodings_matches_reference() {
+    for_each_mutated_encoding(check_decode_matches_reference);
+}
+
+#[test]
+fn decode_random_strings_matches_reference() {
+    for_each_random_string(check_decode_matches_reference);
+}
+
+#[test]
+fn decode_short_strings_matches_reference() {
+    for_each_short_string(check_decode_matches_reference);
+}
+
+fn check_words_errors_match_decode(input: &[u8]) {
+    let words = boba::words(input).try_for_each(|token| token.map(drop));
+    // The decoder stops reading words at a word missing its separator and
+    // may still accept the input, which the tokenizer rejects.
+    let expected = boba::decode(input).and_then(|_| {
+        let enc = &input[1..input.len() - 1];
+        if enc.chunks_exact(6).any(|chunk| chunk[4] != b'-') {
+            Err(boba::DecodeError::Corrupted)
+        } else {
+            Ok(())
+        }
+    });
+    assert_eq!(
+        words,
+        expected,
+        "mismatch tokenizing {:?}",
+        String::from_utf8_lossy(input)
+    );
+}
+
+#[test]
+fn words_errors_match_decode() {
+    for_each_mutated_encoding(check_words_errors_match_decode);
+    for_each_random_string(check_words_errors_match_decode);
+    for_each_short_string(check_words_errors_match_decode);
+}
+
+#[test]
+fn encode_matches_reference() {
+    let mut rng = XorShift(0x5EED_BABB_1E00_0004);
+    for len in 0..1024 {
+        let data = rng.bytes(len);
+        assert_eq!(boba::encode(&data), reference::encode(&data));
+    }
+}
+
+#[test]
+fn encode_all_pairs_matches_reference() {
+    for left in 0..=u8::MAX {
+        assert_eq!(boba::encode([left]), reference::encode(&[left]));
+        for right in 0..=u8::MAX {
+            let data = [left, right, left ^ right];
+            assert_eq!(boba::encode(&data[..2]), reference::encode(&data[..2]));
+            assert_eq!(boba::encode(data), reference::encode(&data));
+        }
+    }
+}